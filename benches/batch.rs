@@ -0,0 +1,63 @@
+//!
+//! Compares the slice conversions in `pitch_calc::batch` against calling the scalar
+//! `pitch_calc::calc` functions one element at a time.
+//!
+//! Run with `cargo bench --bench batch`.
+//!
+
+use pitch_calc::{batch, calc};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const LEN: usize = 4096;
+const ITERATIONS: u32 = 2_000;
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed()
+}
+
+fn report(name: &str, scalar: Duration, batch: Duration) {
+    let per_element = |d: Duration| d.as_nanos() as f64 / (LEN as f64 * ITERATIONS as f64);
+    println!(
+        "{:<16} scalar: {:>6.2}ns/elem  batch: {:>6.2}ns/elem  speedup: {:.2}x",
+        name,
+        per_element(scalar),
+        per_element(batch),
+        scalar.as_secs_f64() / batch.as_secs_f64(),
+    );
+}
+
+macro_rules! bench {
+    ($name:ident, $src:expr, $zero:expr) => {{
+        let src = $src;
+        let mut dst = vec![$zero; LEN];
+        let scalar = time(|| {
+            for (d, &s) in dst.iter_mut().zip(black_box(&src)) {
+                *d = calc::$name(s);
+            }
+            black_box(&dst);
+        });
+        let batch = time(|| {
+            batch::$name(black_box(&src), &mut dst);
+            black_box(&dst);
+        });
+        report(stringify!($name), scalar, batch);
+    }};
+}
+
+fn main() {
+    let hz: Vec<f32> = (0..LEN).map(|i| 20.0 + i as f32 * 4.875).collect();
+    let steps: Vec<f32> = (0..LEN).map(|i| i as f32 / 32.0).collect();
+    let mels: Vec<f32> = hz.iter().map(|&hz| calc::mel_from_hz(hz)).collect();
+
+    bench!(step_from_hz, hz.clone(), 0.0);
+    bench!(hz_from_step, steps.clone(), 0.0);
+    bench!(mel_from_hz, hz.clone(), 0.0);
+    bench!(hz_from_mel, mels.clone(), 0.0);
+    bench!(mel_from_step, steps.clone(), 0.0);
+    bench!(perc_from_hz, hz.clone(), 0.0);
+}
@@ -4,17 +4,17 @@
 
 extern crate pitch_calc;
 
-use pitch_calc::{Hz, Letter, LetterOctave, ScaledPerc, Step};
+use pitch_calc::{Hz, Letter, LetterOctave, Mel, Perc, Pitch, ScaledPerc, Step};
 
 fn main() {
     println!();
 
     // You can convert midi-step to letter octave
-    assert!(Step(64.0).to_letter_octave() == LetterOctave(Letter::E, 4));
+    assert!(Step(64.0).to_pitch::<LetterOctave>() == LetterOctave(Letter::E, 4));
     // Or hz to letter octave.
     assert!(Hz(220.0).letter_octave() == (Letter::A, 3));
     // Or the other way around.
-    assert!(LetterOctave(Letter::A, 4).to_hz() == Hz(440.0));
+    assert!(LetterOctave(Letter::A, 4).to_pitch::<Hz>() == Hz(440.0));
 
     // This will print a bunch of midi-steps in their musical letter form.
     for i in 0..12 {
@@ -63,14 +63,14 @@ fn main() {
 
     // Test a big chain of conversions.
     let a_4 = LetterOctave(Letter::A, 4)
-        .to_hz()
-        .to_perc()
-        .to_mel()
-        .to_step()
-        .to_perc()
-        .to_hz()
-        .to_step()
-        .to_letter_octave()
+        .to_pitch::<Hz>()
+        .to_pitch::<Perc>()
+        .to_pitch::<Mel>()
+        .to_pitch::<Step>()
+        .to_pitch::<Perc>()
+        .to_pitch::<Hz>()
+        .to_pitch::<Step>()
+        .to_pitch::<LetterOctave>()
         .hz()
         .round() as i32;
     assert!(a_4 == 440, "A4 == {:?}", a_4);
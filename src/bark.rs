@@ -0,0 +1,115 @@
+use super::{calc, hz_from_bark, hz_from_bark_with_scale, Hz};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
+
+/// The formula used to convert between frequency in hz and barks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BarkScale {
+    /// `26.81 * hz / (1960 + hz) - 0.53`, with the low and high end corrections described by
+    /// Traunmüller (1990).
+    Traunmuller,
+    /// `13 * atan(0.00076 * hz) + 3.5 * atan((hz / 7500)^2)` as described by Zwicker and Terhardt
+    /// (1980).
+    ///
+    /// This formula has no closed form inverse, so conversions to hz are solved numerically and
+    /// are only defined between 0 and ~25.9 barks.
+    Zwicker,
+}
+
+/// The bark scale used by the `Bark` type and the conversions that do not take a `BarkScale`.
+pub const DEFAULT_BARK_SCALE: BarkScale = BarkScale::Traunmuller;
+
+/// Bark value representation
+///     - based on the critical band rate scale proposed by Zwicker in 1961.
+///
+/// Ordered and hashed by the Bark value with `f32::total_cmp`, so NaN sorts last and equals
+/// itself.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde::BarkRepr"))]
+pub struct Bark(pub calc::Bark);
+
+impl Bark {
+    /// Return the unit value of the Bark struct.
+    #[inline]
+    pub fn bark(self) -> calc::Bark {
+        let Bark(bark) = self;
+        bark
+    }
+
+    /// Convert to hz.
+    #[inline]
+    pub fn hz(self) -> calc::Hz {
+        hz_from_bark(self.bark())
+    }
+
+    /// Convert to hz, interpreting the value as barks on the given scale.
+    #[inline]
+    pub fn hz_with_scale(self, scale: BarkScale) -> calc::Hz {
+        hz_from_bark_with_scale(self.bark(), scale)
+    }
+
+    /// Convert to a Hz struct, interpreting the value as barks on the given scale.
+    #[inline]
+    pub fn to_hz_with_scale(self, scale: BarkScale) -> Hz {
+        Hz(self.hz_with_scale(scale))
+    }
+}
+
+impl Add for Bark {
+    type Output = Bark;
+    #[inline]
+    fn add(self, rhs: Bark) -> Bark {
+        Bark(self.bark() + rhs.bark())
+    }
+}
+
+impl Sub for Bark {
+    type Output = Bark;
+    #[inline]
+    fn sub(self, rhs: Bark) -> Bark {
+        Bark(self.bark() - rhs.bark())
+    }
+}
+
+impl Neg for Bark {
+    type Output = Bark;
+    #[inline]
+    fn neg(self) -> Bark {
+        Bark(-self.bark())
+    }
+}
+
+impl PartialEq for Bark {
+    #[inline]
+    fn eq(&self, other: &Bark) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Bark {}
+
+impl PartialOrd for Bark {
+    #[inline]
+    fn partial_cmp(&self, other: &Bark) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Bark {
+    #[inline]
+    fn cmp(&self, other: &Bark) -> Ordering {
+        self.bark().total_cmp(&other.bark())
+    }
+}
+
+impl Hash for Bark {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bark().to_bits().hash(state);
+    }
+}
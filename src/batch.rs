@@ -0,0 +1,278 @@
+//! Conversions over slices of pitches.
+//!
+//! Each function mirrors the scalar function of the same name in the `calc` module, reading from
+//! a source slice and writing into a destination slice of the same length. The slices are
+//! processed in fixed size chunks so that the compiler is able to auto-vectorize the inner loops.
+//!
+//! All functions panic if the source and destination slices differ in length.
+
+use crate::calc::{self, Hz, Mel, Perc, Step, Weight, PITCH_INDEX, TUNING_PITCH_A4};
+use crate::{Letter, Octave, MAX_HZ, MIN_HZ};
+
+/// The number of elements converted per iteration of the inner loop.
+const LANES: usize = 8;
+
+/// Apply `f` to every element of `src`, writing the results into `dst`.
+#[inline(always)]
+fn map<A, B, F>(src: &[A], dst: &mut [B], f: F)
+where
+    A: Copy,
+    F: Fn(A) -> B,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices must have the same length"
+    );
+    let mut src_chunks = src.chunks_exact(LANES);
+    let mut dst_chunks = dst.chunks_exact_mut(LANES);
+    for (src, dst) in (&mut src_chunks).zip(&mut dst_chunks) {
+        for i in 0..LANES {
+            dst[i] = f(src[i]);
+        }
+    }
+    let remainder = src_chunks.remainder().iter();
+    for (&src, dst) in remainder.zip(dst_chunks.into_remainder()) {
+        *dst = f(src);
+    }
+}
+
+/// The scalar kernels used by the slice conversions.
+///
+/// These are algebraically equivalent to the functions in `calc`, rearranged so that each element
+/// requires at most a single transcendental function call.
+mod kernel {
+    use super::*;
+
+    #[inline(always)]
+    pub fn hz_from_step(step: Step) -> Hz {
+        PITCH_INDEX * ((step - TUNING_PITCH_A4) * (1.0 / 12.0)).exp2()
+    }
+
+    #[inline(always)]
+    pub fn step_from_hz(hz: Hz) -> Step {
+        (hz * (1.0 / PITCH_INDEX)).log2() * 12.0 + TUNING_PITCH_A4
+    }
+
+    #[inline(always)]
+    pub fn hz_from_mel(mel: Mel) -> Hz {
+        ((mel * (1.0 / 2595.0)).exp10() - 1.0) * 700.0
+    }
+
+    #[inline(always)]
+    pub fn mel_from_hz(hz: Hz) -> Mel {
+        (1.0 + hz * (1.0 / 700.0)).log2() * (2595.0 * std::f32::consts::LOG10_2)
+    }
+
+    #[inline(always)]
+    pub fn hz_from_perc(perc: Perc) -> Hz {
+        perc as Hz * (MAX_HZ - MIN_HZ) + MIN_HZ
+    }
+
+    #[inline(always)]
+    pub fn perc_from_hz(hz: Hz) -> Perc {
+        Perc::from(hz - MIN_HZ) * (1.0 / Perc::from(MAX_HZ - MIN_HZ))
+    }
+
+    #[inline(always)]
+    pub fn perc_from_scaled_perc(scaled: Perc, weight: Perc) -> Perc {
+        scaled.powf(weight)
+    }
+
+    #[inline(always)]
+    pub fn scaled_perc_from_perc(perc: Perc, weight: Perc) -> Perc {
+        perc.powf(1.0 / weight)
+    }
+
+    trait Exp10 {
+        fn exp10(self) -> Self;
+    }
+
+    impl Exp10 for f32 {
+        #[inline(always)]
+        fn exp10(self) -> f32 {
+            (self * std::f32::consts::LOG2_10).exp2()
+        }
+    }
+}
+
+/// Implement a slice conversion for each given `name(src: From) -> To` using the given kernel.
+macro_rules! impl_batch {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident($src:ident: $From:ty) -> $To:ty $body:block
+    )*) => {
+        $(
+            $(#[$attr])*
+            #[inline]
+            pub fn $name(src: &[$From], dst: &mut [$To]) {
+                map(src, dst, |$src: $From| $body)
+            }
+        )*
+    };
+}
+
+/// Implement a weighted slice conversion for each given `name(src: From, weight) -> To`.
+macro_rules! impl_batch_weighted {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident($src:ident: $From:ty, $weight:ident) -> $To:ty $body:block
+    )*) => {
+        $(
+            $(#[$attr])*
+            #[inline]
+            pub fn $name(src: &[$From], dst: &mut [$To], weight: Weight) {
+                let $weight = Perc::from(weight);
+                map(src, dst, |$src: $From| $body)
+            }
+        )*
+    };
+}
+
+impl_batch! {
+    /// Calculate hz from each (Letter, Octave).
+    hz_from_letter_octave(lo: (Letter, Octave)) -> Hz {
+        kernel::hz_from_step(calc::step_from_letter_octave(lo.0, lo.1))
+    }
+    /// Calculate hz from each mel.
+    hz_from_mel(mel: Mel) -> Hz { kernel::hz_from_mel(mel) }
+    /// Calculate hz from each percentage.
+    hz_from_perc(perc: Perc) -> Hz { kernel::hz_from_perc(perc) }
+    /// Calculate hz from each step.
+    hz_from_step(step: Step) -> Hz { kernel::hz_from_step(step) }
+    /// Calculate (Letter, Octave) from each hz.
+    letter_octave_from_hz(hz: Hz) -> (Letter, Octave) {
+        calc::letter_octave_from_step(kernel::step_from_hz(hz))
+    }
+    /// Calculate (Letter, Octave) from each mel.
+    letter_octave_from_mel(mel: Mel) -> (Letter, Octave) {
+        calc::letter_octave_from_step(kernel::step_from_hz(kernel::hz_from_mel(mel)))
+    }
+    /// Calculate (Letter, Octave) from each percentage.
+    letter_octave_from_perc(perc: Perc) -> (Letter, Octave) {
+        calc::letter_octave_from_step(kernel::step_from_hz(kernel::hz_from_perc(perc)))
+    }
+    /// Calculate (Letter, Octave) from each step.
+    letter_octave_from_step(step: Step) -> (Letter, Octave) {
+        calc::letter_octave_from_step(step)
+    }
+    /// Calculate mel from each hz.
+    mel_from_hz(hz: Hz) -> Mel { kernel::mel_from_hz(hz) }
+    /// Calculate mel from each (Letter, Octave).
+    mel_from_letter_octave(lo: (Letter, Octave)) -> Mel {
+        kernel::mel_from_hz(kernel::hz_from_step(calc::step_from_letter_octave(lo.0, lo.1)))
+    }
+    /// Calculate mel from each percentage.
+    mel_from_perc(perc: Perc) -> Mel { kernel::mel_from_hz(kernel::hz_from_perc(perc)) }
+    /// Calculate mel from each step.
+    mel_from_step(step: Step) -> Mel { kernel::mel_from_hz(kernel::hz_from_step(step)) }
+    /// Calculate percentage from each hz.
+    perc_from_hz(hz: Hz) -> Perc { kernel::perc_from_hz(hz) }
+    /// Calculate percentage from each (Letter, Octave).
+    perc_from_letter_octave(lo: (Letter, Octave)) -> Perc {
+        kernel::perc_from_hz(kernel::hz_from_step(calc::step_from_letter_octave(lo.0, lo.1)))
+    }
+    /// Calculate percentage from each mel.
+    perc_from_mel(mel: Mel) -> Perc { kernel::perc_from_hz(kernel::hz_from_mel(mel)) }
+    /// Calculate percentage from each step.
+    perc_from_step(step: Step) -> Perc { kernel::perc_from_hz(kernel::hz_from_step(step)) }
+    /// Calculate the step from each hz.
+    step_from_hz(hz: Hz) -> Step { kernel::step_from_hz(hz) }
+    /// Calculate the step from each (Letter, Octave).
+    step_from_letter_octave(lo: (Letter, Octave)) -> Step {
+        calc::step_from_letter_octave(lo.0, lo.1)
+    }
+    /// Calculate the step from each mel.
+    step_from_mel(mel: Mel) -> Step { kernel::step_from_hz(kernel::hz_from_mel(mel)) }
+    /// Calculate the step from each percentage.
+    step_from_perc(perc: Perc) -> Step { kernel::step_from_hz(kernel::hz_from_perc(perc)) }
+}
+
+impl_batch_weighted! {
+    /// Calculate hz from each scaled percentage.
+    hz_from_scaled_perc(scaled: Perc, weight) -> Hz {
+        kernel::hz_from_perc(kernel::perc_from_scaled_perc(scaled, weight))
+    }
+    /// Calculate (Letter, Octave) from each scaled percentage.
+    letter_octave_from_scaled_perc(scaled: Perc, weight) -> (Letter, Octave) {
+        let hz = kernel::hz_from_perc(kernel::perc_from_scaled_perc(scaled, weight));
+        calc::letter_octave_from_step(kernel::step_from_hz(hz))
+    }
+    /// Calculate mel from each scaled percentage.
+    mel_from_scaled_perc(scaled: Perc, weight) -> Mel {
+        kernel::mel_from_hz(kernel::hz_from_perc(kernel::perc_from_scaled_perc(scaled, weight)))
+    }
+    /// Calculate percentage from each scaled percentage.
+    perc_from_scaled_perc(scaled: Perc, weight) -> Perc {
+        kernel::perc_from_scaled_perc(scaled, weight)
+    }
+    /// Calculate the step from each scaled percentage.
+    step_from_scaled_perc(scaled: Perc, weight) -> Step {
+        kernel::step_from_hz(kernel::hz_from_perc(kernel::perc_from_scaled_perc(scaled, weight)))
+    }
+    /// Calculate scaled percentage from each hz.
+    scaled_perc_from_hz(hz: Hz, weight) -> Perc {
+        kernel::scaled_perc_from_perc(kernel::perc_from_hz(hz), weight)
+    }
+    /// Calculate scaled percentage from each (Letter, Octave).
+    scaled_perc_from_letter_octave(lo: (Letter, Octave), weight) -> Perc {
+        let hz = kernel::hz_from_step(calc::step_from_letter_octave(lo.0, lo.1));
+        kernel::scaled_perc_from_perc(kernel::perc_from_hz(hz), weight)
+    }
+    /// Calculate scaled percentage from each mel.
+    scaled_perc_from_mel(mel: Mel, weight) -> Perc {
+        kernel::scaled_perc_from_perc(kernel::perc_from_hz(kernel::hz_from_mel(mel)), weight)
+    }
+    /// Calculate scaled percentage from each percentage.
+    scaled_perc_from_perc(perc: Perc, weight) -> Perc {
+        kernel::scaled_perc_from_perc(perc, weight)
+    }
+    /// Calculate scaled percentage from each step.
+    scaled_perc_from_step(step: Step, weight) -> Perc {
+        kernel::scaled_perc_from_perc(kernel::perc_from_hz(kernel::hz_from_step(step)), weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calc;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() <= 1e-4 * b.abs().max(1.0), "{} != {}", a, b);
+    }
+
+    #[test]
+    fn matches_scalar() {
+        let steps: Vec<f32> = (0..100).map(|i| i as f32 * 1.3).collect();
+        let mut hz = vec![0.0; steps.len()];
+        let mut mel = vec![0.0; steps.len()];
+        let mut round_trip = vec![0.0; steps.len()];
+        super::hz_from_step(&steps, &mut hz);
+        super::mel_from_hz(&hz, &mut mel);
+        super::step_from_mel(&mel, &mut round_trip);
+        for i in 0..steps.len() {
+            assert_close(hz[i], calc::hz_from_step(steps[i]));
+            assert_close(mel[i], calc::mel_from_hz(hz[i]));
+            assert_close(round_trip[i], steps[i]);
+        }
+    }
+
+    #[test]
+    fn matches_scalar_weighted() {
+        let percs: Vec<f64> = (0..=20).map(|i| i as f64 / 20.0).collect();
+        let mut scaled = vec![0.0; percs.len()];
+        let mut perc = vec![0.0; percs.len()];
+        super::scaled_perc_from_perc(&percs, &mut scaled, 3.0);
+        super::perc_from_scaled_perc(&scaled, &mut perc, 3.0);
+        for i in 0..percs.len() {
+            assert!((scaled[i] - calc::scaled_perc_from_perc(percs[i], 3.0)).abs() < 1e-12);
+            assert!((perc[i] - percs[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_lengths() {
+        super::hz_from_step(&[0.0; 4], &mut [0.0; 3]);
+    }
+}
@@ -1,17 +1,76 @@
-use crate::utils::modulo;
-use crate::{Letter, Octave, MAX_HZ, MIN_HZ, TOTAL_LETTERS};
-use num_traits::{Float, FromPrimitive, ToPrimitive};
+//! Conversions between the unit values of each pitch representation.
+//!
+//! Each plain `x_from_y` conversion has a checked `try_x_from_y` counterpart, which rejects NaN,
+//! infinite and non-positive frequencies and results that are out of range. The variants taking
+//! a scale, range, curve or tuning (`*_with_scale`, `*_in_range`, `*_with_curve` and
+//! `*_with_tuning`) and `erb_bandwidth_from_hz` have none: their parameters are validated where
+//! they are constructed, e.g. by `FrequencyRange::new` or `Curve::validate`, and their results
+//! may be checked with `is_finite`. The conversions to and from whole semitones cannot fail.
+
+use crate::{
+    BarkScale, Curve, FrequencyRange, Letter, MelScale, Octave, PitchError, DEFAULT_BARK_SCALE,
+    DEFAULT_FREQUENCY_RANGE, TOTAL_LETTERS,
+};
+use num_traits::Float;
 
 /// Useful for conversions between Step and Hz.
 #[allow(clippy::excessive_precision)]
 const TWELFTH_ROOT_OF_TWO: f32 = 1.059463094359;
 /// The pitch `A 4` represented in steps.
-const TUNING_PITCH_A4: f32 = 69.0;
+pub(crate) const TUNING_PITCH_A4: f32 = 69.0;
 /// The pitch `A 4` represented in hz.
-const PITCH_INDEX: f32 = 440.0;
+pub(crate) const PITCH_INDEX: f32 = 440.0;
+/// The standard tuning of `A 4` in hz, used by all conversions that do not take a tuning.
+pub const DEFAULT_TUNING: Hz = PITCH_INDEX;
 /// Octave offset to match MIDI step standard (i.e. A4 == 69).
 const MIDI_OCTAVE_OFFSET: Octave = 1;
+/// The ratio of each of the twelve equal tempered semitones within an octave to the octave's root.
+///
+/// Used for the const conversions as `powf` cannot be evaluated at compile time.
+#[allow(clippy::excessive_precision)]
+const SEMITONE_RATIOS: [f64; 12] = [
+    1.0,
+    1.059_463_094_359_295_3,
+    1.122_462_048_309_373,
+    1.189_207_115_002_721,
+    1.259_921_049_894_873_2,
+    1.334_839_854_170_034_4,
+    std::f64::consts::SQRT_2,
+    1.498_307_076_876_681_5,
+    1.587_401_051_968_199_5,
+    1.681_792_830_507_429,
+    1.781_797_436_280_678_5,
+    1.887_748_625_363_386_8,
+];
+
+/// The width of each mel in hz within the linear region of the Slaney mel scale.
+const SLANEY_HZ_PER_MEL: Hz = 200.0 / 3.0;
+/// The frequency at which the Slaney mel scale becomes logarithmic.
+const SLANEY_MIN_LOG_HZ: Hz = 1000.0;
+/// The Slaney mel at which the scale becomes logarithmic.
+const SLANEY_MIN_LOG_MEL: Mel = SLANEY_MIN_LOG_HZ / SLANEY_HZ_PER_MEL;
+/// The step size of the logarithmic region of the Slaney mel scale, i.e. `ln(6.4) / 27`.
+#[allow(clippy::excessive_precision)]
+const SLANEY_LOG_STEP: f32 = 0.068_751_777_420_949_12;
+
+/// The total number of MIDI notes.
+pub const TOTAL_MIDI_NOTES: usize = 128;
+
+/// The frequency in hz of every MIDI note, indexed by note number.
+///
+/// Evaluated at compile time, so may be used to avoid calling `powf` at runtime.
+pub const MIDI_HZ: [Hz; TOTAL_MIDI_NOTES] = {
+    let mut table = [0.0; TOTAL_MIDI_NOTES];
+    let mut note = 0;
+    while note < TOTAL_MIDI_NOTES {
+        table[note] = hz_from_semitones(note as Semitones);
+        note += 1;
+    }
+    table
+};
 
+pub type Bark = f32;
+pub type Erb = f32;
 pub type Hz = f32;
 pub type Mel = f32;
 pub type Perc = f64;
@@ -19,34 +78,158 @@ pub type Semitones = i32;
 pub type Step = f32;
 pub type Weight = f32;
 
+/// Calculate barks from hz on the default bark scale.
+#[inline]
+pub fn bark_from_hz(hz: Hz) -> Bark {
+    bark_from_hz_with_scale(hz, DEFAULT_BARK_SCALE)
+}
+
+/// Calculate barks from hz on the given bark scale.
+#[inline]
+pub fn bark_from_hz_with_scale(hz: Hz, scale: BarkScale) -> Bark {
+    match scale {
+        BarkScale::Traunmuller => match 26.81 * hz / (1960.0 + hz) - 0.53 {
+            bark if bark < 2.0 => bark + 0.15 * (2.0 - bark),
+            bark if bark > 20.1 => bark + 0.22 * (bark - 20.1),
+            bark => bark,
+        },
+        BarkScale::Zwicker => zwicker_bark_from_hz(f64::from(hz)) as Bark,
+    }
+}
+
+/// The Zwicker and Terhardt critical band rate.
+#[inline]
+fn zwicker_bark_from_hz(hz: f64) -> f64 {
+    13.0 * (0.00076 * hz).atan() + 3.5 * (hz / 7500.0).powi(2).atan()
+}
+
+/// Calculate the ERB-rate from hz.
+/// Formula from Glasberg and Moore (1990).
+#[inline]
+pub fn erb_from_hz(hz: Hz) -> Erb {
+    (1.0 + 0.00437 * hz).log10() * 21.4
+}
+
+/// Calculate the equivalent rectangular bandwidth in hz of the auditory filter centred on `hz`.
+/// Formula from Glasberg and Moore (1990).
+#[inline]
+pub fn erb_bandwidth_from_hz(hz: Hz) -> Hz {
+    24.7 * (0.00437 * hz + 1.0)
+}
+
 /// Find and return the smallest distance
 /// between two letters in semitones as an int.
+///
+/// Enharmonic letters are the same distance apart, and the result lies within `0..=6`.
 #[inline]
 pub fn difference_in_semitones(letter_a: Letter, letter_b: Letter) -> Semitones {
-    let diff = (letter_a as Semitones - letter_b as Semitones).abs();
-    if diff > 6 {
-        diff - 12
-    } else {
-        diff
+    let diff = (letter_a.pitch_class() as Semitones - letter_b.pitch_class() as Semitones).abs();
+    diff.min(12 - diff)
+}
+
+/// Calculate hz from barks on the default bark scale.
+#[inline]
+pub fn hz_from_bark(bark: Bark) -> Hz {
+    hz_from_bark_with_scale(bark, DEFAULT_BARK_SCALE)
+}
+
+/// Calculate hz from barks on the given bark scale.
+///
+/// The Zwicker scale is inverted numerically and returns NaN for barks outside of the range of
+/// the formula (0 to ~25.9).
+#[inline]
+pub fn hz_from_bark_with_scale(bark: Bark, scale: BarkScale) -> Hz {
+    match scale {
+        BarkScale::Traunmuller => {
+            let bark = match bark {
+                bark if bark < 2.0 => (bark - 0.3) / 0.85,
+                bark if bark > 20.1 => (bark + 4.422) / 1.22,
+                bark => bark,
+            };
+            1960.0 * (bark + 0.53) / (26.28 - bark)
+        }
+        BarkScale::Zwicker => {
+            let bark = f64::from(bark);
+            let (mut low, mut high) = (0.0, 1.0e7);
+            if !(0.0..zwicker_bark_from_hz(high)).contains(&bark) {
+                return Hz::NAN;
+            }
+            // The formula is monotonic for positive frequencies, so bisect until converged.
+            for _ in 0..64 {
+                let mid = 0.5 * (low + high);
+                if zwicker_bark_from_hz(mid) < bark {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            (0.5 * (low + high)) as Hz
+        }
     }
 }
 
+/// Calculate hz from the ERB-rate.
+#[inline]
+pub fn hz_from_erb(erb: Erb) -> Hz {
+    (10.0.powf(erb / 21.4) - 1.0) / 0.00437
+}
+
 /// Calculate hz from (Letter, Octave).
 #[inline]
 pub fn hz_from_letter_octave(letter: Letter, octave: Octave) -> Hz {
     hz_from_step(step_from_letter_octave(letter, octave))
 }
 
+/// Calculate hz from a whole number of MIDI steps.
+///
+/// Unlike `hz_from_step` this may be evaluated at compile time.
+#[inline]
+pub const fn hz_from_semitones(step: Semitones) -> Hz {
+    let from_a4 = step - TUNING_PITCH_A4 as Semitones;
+    let semitone = from_a4.rem_euclid(TOTAL_LETTERS as Semitones);
+    let mut octaves = from_a4.div_euclid(TOTAL_LETTERS as Semitones);
+    let mut hz = PITCH_INDEX as f64 * SEMITONE_RATIOS[semitone as usize];
+    while octaves > 0 {
+        hz *= 2.0;
+        octaves -= 1;
+    }
+    while octaves < 0 {
+        hz /= 2.0;
+        octaves += 1;
+    }
+    hz as Hz
+}
+
 /// Calculate hz from mel.
 #[inline]
 pub fn hz_from_mel(mel: Mel) -> Hz {
     (10.0.powf(mel / 2595.0) - 1.0) * 700.0
 }
 
+/// Calculate hz from mel on the given mel scale.
+#[inline]
+pub fn hz_from_mel_with_scale(mel: Mel, scale: MelScale) -> Hz {
+    match scale {
+        MelScale::OShaughnessy => hz_from_mel(mel),
+        MelScale::Htk => ((mel / 1127.0).exp() - 1.0) * 700.0,
+        MelScale::Slaney if mel < SLANEY_MIN_LOG_MEL => mel * SLANEY_HZ_PER_MEL,
+        MelScale::Slaney => {
+            SLANEY_MIN_LOG_HZ * ((mel - SLANEY_MIN_LOG_MEL) * SLANEY_LOG_STEP).exp()
+        }
+        MelScale::Fant => (2.0.powf(mel / 1000.0) - 1.0) * 1000.0,
+    }
+}
+
 /// Calculate frequency in hz from percentage.
 #[inline]
 pub fn hz_from_perc(perc: Perc) -> Hz {
-    perc as Hz * (MAX_HZ - MIN_HZ) + MIN_HZ
+    hz_from_perc_in_range(perc, DEFAULT_FREQUENCY_RANGE)
+}
+
+/// Calculate frequency in hz from a percentage of the given frequency range.
+#[inline]
+pub fn hz_from_perc_in_range(perc: Perc, range: FrequencyRange) -> Hz {
+    perc as Hz * range.span() + range.min()
 }
 
 /// Calculate hz from scaled percentage.
@@ -55,12 +238,30 @@ pub fn hz_from_scaled_perc(scaled: Perc, weight: Weight) -> Hz {
     hz_from_perc(perc_from_scaled_perc(scaled, weight))
 }
 
+/// Calculate hz from a percentage scaled along the given curve.
+#[inline]
+pub fn hz_from_scaled_perc_with_curve<C: Curve>(scaled: Perc, curve: C) -> Hz {
+    hz_from_perc(perc_from_scaled_perc_with_curve(scaled, curve))
+}
+
+/// Calculate hz from a scaled percentage of the given frequency range.
+#[inline]
+pub fn hz_from_scaled_perc_in_range(scaled: Perc, weight: Weight, range: FrequencyRange) -> Hz {
+    hz_from_perc_in_range(perc_from_scaled_perc(scaled, weight), range)
+}
+
 /// Calculate hz from pitch as `step`.
 #[inline]
 pub fn hz_from_step(step: Step) -> Hz {
     PITCH_INDEX * TWELFTH_ROOT_OF_TWO.powf(step - TUNING_PITCH_A4)
 }
 
+/// Calculate hz from pitch as `step`, where A4 is tuned to the frequency `a4`.
+#[inline]
+pub fn hz_from_step_with_tuning(step: Step, a4: Hz) -> Hz {
+    a4 * ((step - TUNING_PITCH_A4) / 12.0).exp2()
+}
+
 /// Calculate (Letter, Octave) from hz.
 #[inline]
 pub fn letter_octave_from_hz(hz: Hz) -> (Letter, Octave) {
@@ -88,11 +289,16 @@ pub fn letter_octave_from_scaled_perc(scaled: Perc, weight: Weight) -> (Letter,
 /// Calculate pitch as (Letter, Octave) from pitch as step.
 #[inline]
 pub fn letter_octave_from_step(step: Step) -> (Letter, Octave) {
-    let rounded = step.round() as Octave;
-    let letter_step = modulo(rounded, Octave::from(TOTAL_LETTERS));
+    letter_octave_from_semitones(step.round() as Semitones)
+}
+
+/// Calculate pitch as (Letter, Octave) from a whole number of MIDI steps.
+#[inline]
+pub const fn letter_octave_from_semitones(step: Semitones) -> (Letter, Octave) {
+    let letter_step = step.rem_euclid(TOTAL_LETTERS as Semitones);
     (
-        FromPrimitive::from_i32(letter_step).unwrap(),
-        (rounded - letter_step) / 12 - MIDI_OCTAVE_OFFSET,
+        Letter::from_pitch_class(letter_step as i64),
+        (step - letter_step) / 12 - MIDI_OCTAVE_OFFSET,
     )
 }
 
@@ -103,6 +309,18 @@ pub fn mel_from_hz(hz: Hz) -> Mel {
     (1.0 + hz / 700.0).log10() * 2595.0
 }
 
+/// Calculate mel from hz on the given mel scale.
+#[inline]
+pub fn mel_from_hz_with_scale(hz: Hz, scale: MelScale) -> Mel {
+    match scale {
+        MelScale::OShaughnessy => mel_from_hz(hz),
+        MelScale::Htk => (1.0 + hz / 700.0).ln() * 1127.0,
+        MelScale::Slaney if hz < SLANEY_MIN_LOG_HZ => hz / SLANEY_HZ_PER_MEL,
+        MelScale::Slaney => SLANEY_MIN_LOG_MEL + (hz / SLANEY_MIN_LOG_HZ).ln() / SLANEY_LOG_STEP,
+        MelScale::Fant => (1.0 + hz / 1000.0).log2() * 1000.0,
+    }
+}
+
 /// Calculate mel from (Letter, Octave).
 #[inline]
 pub fn mel_from_letter_octave(letter: Letter, octave: Octave) -> Mel {
@@ -130,7 +348,13 @@ pub fn mel_from_step(step: Step) -> Mel {
 /// Calculate percentage from hz.
 #[inline]
 pub fn perc_from_hz(hz: Hz) -> Perc {
-    Perc::from(hz - MIN_HZ) / Perc::from(MAX_HZ - MIN_HZ)
+    perc_from_hz_in_range(hz, DEFAULT_FREQUENCY_RANGE)
+}
+
+/// Calculate the percentage of the given frequency range from hz.
+#[inline]
+pub fn perc_from_hz_in_range(hz: Hz, range: FrequencyRange) -> Perc {
+    Perc::from(hz - range.min()) / Perc::from(range.span())
 }
 
 /// Calculate percentage from letter octave.
@@ -148,7 +372,13 @@ pub fn perc_from_mel(mel: Mel) -> Perc {
 /// Calculate percentage from scaled percentage.
 #[inline]
 pub fn perc_from_scaled_perc(scaled: Perc, weight: Weight) -> Perc {
-    scaled.powf(Perc::from(weight))
+    perc_from_scaled_perc_with_curve(scaled, weight)
+}
+
+/// Calculate percentage from a percentage scaled along the given curve.
+#[inline]
+pub fn perc_from_scaled_perc_with_curve<C: Curve>(scaled: Perc, curve: C) -> Perc {
+    curve.unscale(scaled)
 }
 
 /// Calculate frequency percentage from pitch as `step`.
@@ -163,6 +393,18 @@ pub fn scaled_perc_from_hz(hz: Hz, weight: Weight) -> Perc {
     scaled_perc_from_perc(perc_from_hz(hz), weight)
 }
 
+/// Calculate the percentage scaled along the given curve from hz.
+#[inline]
+pub fn scaled_perc_from_hz_with_curve<C: Curve>(hz: Hz, curve: C) -> Perc {
+    scaled_perc_from_perc_with_curve(perc_from_hz(hz), curve)
+}
+
+/// Calculate the scaled percentage of the given frequency range from hz.
+#[inline]
+pub fn scaled_perc_from_hz_in_range(hz: Hz, weight: Weight, range: FrequencyRange) -> Perc {
+    scaled_perc_from_perc(perc_from_hz_in_range(hz, range), weight)
+}
+
 /// Calculate scaled percentage from letter octave.
 #[inline]
 pub fn scaled_perc_from_letter_octave(letter: Letter, octave: Octave, weight: Weight) -> Perc {
@@ -178,7 +420,13 @@ pub fn scaled_perc_from_mel(mel: Mel, weight: Weight) -> Perc {
 /// Calculate scaled percentage from percentage.
 #[inline]
 pub fn scaled_perc_from_perc(perc: Perc, weight: Weight) -> Perc {
-    perc.powf(1.0 / Perc::from(weight))
+    scaled_perc_from_perc_with_curve(perc, weight)
+}
+
+/// Calculate the percentage scaled along the given curve from percentage.
+#[inline]
+pub fn scaled_perc_from_perc_with_curve<C: Curve>(perc: Perc, curve: C) -> Perc {
+    curve.scale(perc)
 }
 
 /// Calculate scaled frequency percentage from pitch as `step`.
@@ -193,10 +441,22 @@ pub fn step_from_hz(hz: Hz) -> Step {
     (hz / PITCH_INDEX).log2() / TWELFTH_ROOT_OF_TWO.log2() + TUNING_PITCH_A4
 }
 
+/// Calculate the pitch `step` from frequency in hz, where A4 is tuned to the frequency `a4`.
+#[inline]
+pub fn step_from_hz_with_tuning(hz: Hz, a4: Hz) -> Step {
+    (hz / a4).log2() * 12.0 + TUNING_PITCH_A4
+}
+
 /// Calculate the pitch `step` from (Letter, Octave).
 #[inline]
-pub fn step_from_letter_octave(letter: Letter, octave: Octave) -> Step {
-    (MIDI_OCTAVE_OFFSET + octave) as Step * 12.0 + letter.to_f32().unwrap()
+pub const fn step_from_letter_octave(letter: Letter, octave: Octave) -> Step {
+    semitones_from_letter_octave(letter, octave) as Step
+}
+
+/// Calculate the whole number of MIDI steps from (Letter, Octave).
+#[inline]
+pub const fn semitones_from_letter_octave(letter: Letter, octave: Octave) -> Semitones {
+    (MIDI_OCTAVE_OFFSET + octave) * TOTAL_LETTERS as Semitones + letter.pitch_class() as Semitones
 }
 
 /// Calculate the pitch `step` from mel.
@@ -216,3 +476,459 @@ pub fn step_from_perc(perc: Perc) -> Step {
 pub fn step_from_scaled_perc(scaled: Perc, weight: Weight) -> Step {
     step_from_hz(hz_from_scaled_perc(scaled, weight))
 }
+
+/// The largest magnitude of step whose frequency is finite and positive, i.e. 124 octaves either
+/// side of C-1, beyond which the checked conversions consider the step or octave out of range.
+pub(crate) const MAX_STEP: Step = 1_488.0;
+
+/// Check that the value is neither NaN nor infinite.
+#[inline]
+fn check_finite<F: Float>(value: F) -> Result<F, PitchError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(PitchError::NonFinite)
+    }
+}
+
+/// Apply the conversion to the finite `input`, checking that the result is finite.
+#[inline]
+fn convert<F: Float, G: Float>(input: F, conversion: impl FnOnce(F) -> G) -> Result<G, PitchError> {
+    let output = conversion(check_finite(input)?);
+    if output.is_finite() {
+        Ok(output)
+    } else {
+        Err(PitchError::OutOfRange(input.to_f64().unwrap_or(f64::NAN)))
+    }
+}
+
+/// Check that the frequency is finite and positive.
+#[inline]
+pub(crate) fn check_hz(hz: Hz) -> Result<Hz, PitchError> {
+    match check_finite(hz)? {
+        hz if hz > 0.0 => Ok(hz),
+        hz => Err(PitchError::NonPositiveFrequency(hz)),
+    }
+}
+
+/// Check that the step is finite and that its frequency is finite and positive.
+#[inline]
+fn check_step(step: Step) -> Result<Step, PitchError> {
+    match check_finite(step)? {
+        step if step.abs() <= MAX_STEP => Ok(step),
+        step => Err(PitchError::OutOfRange(step.into())),
+    }
+}
+
+/// Check that the nearest whole step lies within the range of representable octaves.
+#[inline]
+fn check_octave(step: Step) -> Result<Step, PitchError> {
+    match check_finite(step)? {
+        step if step.round().abs() <= MAX_STEP => Ok(step),
+        _ => Err(PitchError::OctaveOutOfRange),
+    }
+}
+
+/// Check that the weight is finite and positive.
+#[inline]
+pub(crate) fn check_weight(weight: Weight) -> Result<Weight, PitchError> {
+    if weight.is_finite() && weight > 0.0 {
+        Ok(weight)
+    } else {
+        Err(PitchError::InvalidWeight(weight))
+    }
+}
+
+/// Checked version of `bark_from_hz`.
+#[inline]
+pub fn try_bark_from_hz(hz: Hz) -> Result<Bark, PitchError> {
+    convert(check_hz(hz)?, bark_from_hz)
+}
+
+/// Checked version of `erb_from_hz`.
+#[inline]
+pub fn try_erb_from_hz(hz: Hz) -> Result<Erb, PitchError> {
+    convert(check_hz(hz)?, erb_from_hz)
+}
+
+/// Checked version of `hz_from_bark`.
+#[inline]
+pub fn try_hz_from_bark(bark: Bark) -> Result<Hz, PitchError> {
+    check_hz(convert(bark, hz_from_bark)?)
+}
+
+/// Checked version of `hz_from_erb`.
+#[inline]
+pub fn try_hz_from_erb(erb: Erb) -> Result<Hz, PitchError> {
+    check_hz(convert(erb, hz_from_erb)?)
+}
+
+/// Checked version of `hz_from_letter_octave`.
+#[inline]
+pub fn try_hz_from_letter_octave(letter: Letter, octave: Octave) -> Result<Hz, PitchError> {
+    try_hz_from_step(try_step_from_letter_octave(letter, octave)?)
+}
+
+/// Checked version of `hz_from_mel`.
+#[inline]
+pub fn try_hz_from_mel(mel: Mel) -> Result<Hz, PitchError> {
+    check_hz(convert(mel, hz_from_mel)?)
+}
+
+/// Checked version of `hz_from_perc`.
+#[inline]
+pub fn try_hz_from_perc(perc: Perc) -> Result<Hz, PitchError> {
+    check_hz(convert(perc, hz_from_perc)?)
+}
+
+/// Checked version of `hz_from_scaled_perc`.
+#[inline]
+pub fn try_hz_from_scaled_perc(scaled: Perc, weight: Weight) -> Result<Hz, PitchError> {
+    try_hz_from_perc(try_perc_from_scaled_perc(scaled, weight)?)
+}
+
+/// Checked version of `hz_from_step`.
+#[inline]
+pub fn try_hz_from_step(step: Step) -> Result<Hz, PitchError> {
+    check_hz(hz_from_step(check_step(step)?))
+}
+
+/// Checked version of `letter_octave_from_hz`.
+#[inline]
+pub fn try_letter_octave_from_hz(hz: Hz) -> Result<(Letter, Octave), PitchError> {
+    try_letter_octave_from_step(try_step_from_hz(hz)?)
+}
+
+/// Checked version of `letter_octave_from_mel`.
+#[inline]
+pub fn try_letter_octave_from_mel(mel: Mel) -> Result<(Letter, Octave), PitchError> {
+    try_letter_octave_from_hz(try_hz_from_mel(mel)?)
+}
+
+/// Checked version of `letter_octave_from_perc`.
+#[inline]
+pub fn try_letter_octave_from_perc(perc: Perc) -> Result<(Letter, Octave), PitchError> {
+    try_letter_octave_from_step(try_step_from_perc(perc)?)
+}
+
+/// Checked version of `letter_octave_from_scaled_perc`.
+#[inline]
+pub fn try_letter_octave_from_scaled_perc(
+    scaled: Perc,
+    weight: Weight,
+) -> Result<(Letter, Octave), PitchError> {
+    try_letter_octave_from_step(try_step_from_scaled_perc(scaled, weight)?)
+}
+
+/// Checked version of `letter_octave_from_step`.
+#[inline]
+pub fn try_letter_octave_from_step(step: Step) -> Result<(Letter, Octave), PitchError> {
+    Ok(letter_octave_from_step(check_octave(step)?))
+}
+
+/// Checked version of `mel_from_hz`.
+#[inline]
+pub fn try_mel_from_hz(hz: Hz) -> Result<Mel, PitchError> {
+    convert(check_hz(hz)?, mel_from_hz)
+}
+
+/// Checked version of `mel_from_letter_octave`.
+#[inline]
+pub fn try_mel_from_letter_octave(letter: Letter, octave: Octave) -> Result<Mel, PitchError> {
+    try_mel_from_hz(try_hz_from_letter_octave(letter, octave)?)
+}
+
+/// Checked version of `mel_from_perc`.
+#[inline]
+pub fn try_mel_from_perc(perc: Perc) -> Result<Mel, PitchError> {
+    try_mel_from_hz(try_hz_from_perc(perc)?)
+}
+
+/// Checked version of `mel_from_scaled_perc`.
+#[inline]
+pub fn try_mel_from_scaled_perc(scaled: Perc, weight: Weight) -> Result<Mel, PitchError> {
+    try_mel_from_hz(try_hz_from_scaled_perc(scaled, weight)?)
+}
+
+/// Checked version of `mel_from_step`.
+#[inline]
+pub fn try_mel_from_step(step: Step) -> Result<Mel, PitchError> {
+    try_mel_from_hz(try_hz_from_step(step)?)
+}
+
+/// Checked version of `perc_from_hz`.
+#[inline]
+pub fn try_perc_from_hz(hz: Hz) -> Result<Perc, PitchError> {
+    convert(check_hz(hz)?, perc_from_hz)
+}
+
+/// Checked version of `perc_from_letter_octave`.
+#[inline]
+pub fn try_perc_from_letter_octave(letter: Letter, octave: Octave) -> Result<Perc, PitchError> {
+    try_perc_from_hz(try_hz_from_letter_octave(letter, octave)?)
+}
+
+/// Checked version of `perc_from_mel`.
+#[inline]
+pub fn try_perc_from_mel(mel: Mel) -> Result<Perc, PitchError> {
+    try_perc_from_hz(try_hz_from_mel(mel)?)
+}
+
+/// Checked version of `perc_from_scaled_perc`.
+#[inline]
+pub fn try_perc_from_scaled_perc(scaled: Perc, weight: Weight) -> Result<Perc, PitchError> {
+    let weight = check_weight(weight)?;
+    convert(scaled, |scaled| perc_from_scaled_perc(scaled, weight))
+}
+
+/// Checked version of `perc_from_step`.
+#[inline]
+pub fn try_perc_from_step(step: Step) -> Result<Perc, PitchError> {
+    try_perc_from_hz(try_hz_from_step(step)?)
+}
+
+/// Checked version of `scaled_perc_from_hz`.
+#[inline]
+pub fn try_scaled_perc_from_hz(hz: Hz, weight: Weight) -> Result<Perc, PitchError> {
+    try_scaled_perc_from_perc(try_perc_from_hz(hz)?, weight)
+}
+
+/// Checked version of `scaled_perc_from_letter_octave`.
+#[inline]
+pub fn try_scaled_perc_from_letter_octave(
+    letter: Letter,
+    octave: Octave,
+    weight: Weight,
+) -> Result<Perc, PitchError> {
+    try_scaled_perc_from_hz(try_hz_from_letter_octave(letter, octave)?, weight)
+}
+
+/// Checked version of `scaled_perc_from_mel`.
+#[inline]
+pub fn try_scaled_perc_from_mel(mel: Mel, weight: Weight) -> Result<Perc, PitchError> {
+    try_scaled_perc_from_hz(try_hz_from_mel(mel)?, weight)
+}
+
+/// Checked version of `scaled_perc_from_perc`.
+#[inline]
+pub fn try_scaled_perc_from_perc(perc: Perc, weight: Weight) -> Result<Perc, PitchError> {
+    let weight = check_weight(weight)?;
+    convert(perc, |perc| scaled_perc_from_perc(perc, weight))
+}
+
+/// Checked version of `scaled_perc_from_step`.
+#[inline]
+pub fn try_scaled_perc_from_step(step: Step, weight: Weight) -> Result<Perc, PitchError> {
+    try_scaled_perc_from_hz(try_hz_from_step(step)?, weight)
+}
+
+/// Checked version of `step_from_hz`.
+#[inline]
+pub fn try_step_from_hz(hz: Hz) -> Result<Step, PitchError> {
+    convert(check_hz(hz)?, step_from_hz)
+}
+
+/// Checked version of `step_from_letter_octave`.
+#[inline]
+pub fn try_step_from_letter_octave(letter: Letter, octave: Octave) -> Result<Step, PitchError> {
+    let step = octave
+        .checked_add(MIDI_OCTAVE_OFFSET)
+        .and_then(|octave| octave.checked_mul(TOTAL_LETTERS as Semitones))
+        .and_then(|step| step.checked_add(letter.pitch_class() as Semitones))
+        .ok_or(PitchError::OctaveOutOfRange)?;
+    check_octave(step as Step)
+}
+
+/// Checked version of `step_from_mel`.
+#[inline]
+pub fn try_step_from_mel(mel: Mel) -> Result<Step, PitchError> {
+    try_step_from_hz(try_hz_from_mel(mel)?)
+}
+
+/// Checked version of `step_from_perc`.
+#[inline]
+pub fn try_step_from_perc(perc: Perc) -> Result<Step, PitchError> {
+    try_step_from_hz(try_hz_from_perc(perc)?)
+}
+
+/// Checked version of `step_from_scaled_perc`.
+#[inline]
+pub fn try_step_from_scaled_perc(scaled: Perc, weight: Weight) -> Result<Step, PitchError> {
+    try_step_from_hz(try_hz_from_scaled_perc(scaled, weight)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hz as HzType, LetterOctave};
+
+    const A4: HzType = LetterOctave(Letter::A, 4).to_hz_const();
+
+    #[test]
+    fn const_hz_matches_hz_from_step() {
+        assert_eq!(A4, HzType(440.0));
+        for (note, &hz) in MIDI_HZ.iter().enumerate() {
+            let expected = hz_from_step(note as Step);
+            assert!(
+                (hz - expected).abs() / expected < 1e-5,
+                "{} != {}",
+                hz,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn semitone_differences() {
+        assert_eq!(difference_in_semitones(Letter::C, Letter::D), 2);
+        assert_eq!(difference_in_semitones(Letter::Db, Letter::Csh), 0);
+        assert_eq!(difference_in_semitones(Letter::C, Letter::G), 5);
+        assert_eq!(difference_in_semitones(Letter::B, Letter::C), 1);
+        assert_eq!(difference_in_semitones(Letter::F, Letter::B), 6);
+    }
+
+    #[test]
+    fn checked_conversions() {
+        assert_eq!(try_hz_from_step(69.0), Ok(440.0));
+        assert_eq!(
+            try_step_from_hz(0.0),
+            Err(PitchError::NonPositiveFrequency(0.0))
+        );
+        assert_eq!(try_step_from_hz(Hz::NAN), Err(PitchError::NonFinite));
+        assert_eq!(
+            try_hz_from_step(2_000.0),
+            Err(PitchError::OutOfRange(2_000.0))
+        );
+        assert_eq!(try_hz_from_mel(1e6), Err(PitchError::OutOfRange(1e6)));
+        assert_eq!(try_hz_from_perc(1e36), Err(PitchError::OutOfRange(1e36)));
+        assert_eq!(try_hz_from_step(Step::NAN), Err(PitchError::NonFinite));
+        for step in [-MAX_STEP, MAX_STEP] {
+            let hz = try_hz_from_step(step).unwrap();
+            assert!(hz.is_normal() && hz > 0.0, "{}", hz);
+        }
+        let (letter, octave) = letter_octave_from_step(MAX_STEP);
+        assert!(try_hz_from_letter_octave(letter, octave).is_ok());
+        assert_eq!(
+            try_hz_from_letter_octave(letter, octave + 1),
+            Err(PitchError::OctaveOutOfRange)
+        );
+        assert_eq!(
+            try_letter_octave_from_step(Step::MAX),
+            Err(PitchError::OctaveOutOfRange)
+        );
+        assert_eq!(
+            try_step_from_letter_octave(Letter::C, Octave::MAX),
+            Err(PitchError::OctaveOutOfRange)
+        );
+        assert_eq!(
+            try_perc_from_scaled_perc(0.5, 0.0),
+            Err(PitchError::InvalidWeight(0.0))
+        );
+        assert_eq!(
+            try_letter_octave_from_perc(0.5),
+            Ok(letter_octave_from_perc(0.5))
+        );
+    }
+
+    #[test]
+    fn mel_scales() {
+        // Reference values from librosa's `hz_to_mel` (with and without `htk=True`).
+        let assert_close = |a: Mel, b: Mel| assert!((a - b).abs() < 1e-3 * b.abs(), "{} {}", a, b);
+        assert_close(mel_from_hz_with_scale(440.0, MelScale::Slaney), 6.6);
+        assert_close(mel_from_hz_with_scale(4000.0, MelScale::Slaney), 35.163_76);
+        assert_close(
+            mel_from_hz_with_scale(440.0, MelScale::OShaughnessy),
+            549.638_7,
+        );
+        assert_close(mel_from_hz_with_scale(8000.0, MelScale::Htk), 2_840.037_7);
+        assert_close(mel_from_hz_with_scale(1000.0, MelScale::Fant), 1000.0);
+        for &scale in &[
+            MelScale::OShaughnessy,
+            MelScale::Htk,
+            MelScale::Slaney,
+            MelScale::Fant,
+        ] {
+            for &hz in &[20.0, 440.0, 999.0, 1000.0, 4000.0, 20_000.0] {
+                let mel = mel_from_hz_with_scale(hz, scale);
+                assert_close(hz_from_mel_with_scale(mel, scale), hz);
+            }
+        }
+    }
+
+    #[test]
+    fn bark_and_erb_scales() {
+        let assert_close = |a: Hz, b: Hz| assert!((a - b).abs() < 1e-3 * b.abs(), "{} {}", a, b);
+        assert_close(bark_from_hz_with_scale(1000.0, BarkScale::Zwicker), 8.511);
+        assert_close(
+            bark_from_hz_with_scale(1000.0, BarkScale::Traunmuller),
+            8.527,
+        );
+        assert_close(erb_from_hz(1000.0), 15.621);
+        assert_close(erb_bandwidth_from_hz(1000.0), 132.639);
+        for &hz in &[20.0, 100.0, 440.0, 1000.0, 4000.0, 15_000.0] {
+            for &scale in &[BarkScale::Traunmuller, BarkScale::Zwicker] {
+                let bark = bark_from_hz_with_scale(hz, scale);
+                assert_close(hz_from_bark_with_scale(bark, scale), hz);
+            }
+            assert_close(hz_from_erb(erb_from_hz(hz)), hz);
+        }
+        assert!(hz_from_bark_with_scale(30.0, BarkScale::Zwicker).is_nan());
+        assert_eq!(
+            try_hz_from_bark(bark_from_hz(440.0)).map(Hz::round),
+            Ok(440.0)
+        );
+        assert!(matches!(
+            try_hz_from_bark(30.0),
+            Err(PitchError::NonPositiveFrequency(_))
+        ));
+        assert_eq!(try_hz_from_erb(1e6), Err(PitchError::OutOfRange(1e6)));
+        assert_eq!(
+            try_erb_from_hz(-1.0),
+            Err(PitchError::NonPositiveFrequency(-1.0))
+        );
+        assert_eq!(try_bark_from_hz(Hz::INFINITY), Err(PitchError::NonFinite));
+    }
+
+    #[test]
+    fn tuning() {
+        assert_eq!(hz_from_step_with_tuning(69.0, 442.0), 442.0);
+        assert_eq!(hz_from_step_with_tuning(57.0, 442.0), 221.0);
+        assert!((step_from_hz_with_tuning(440.0, 440.0) - step_from_hz(440.0)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn semitones_round_trip() {
+        for step in -24..152 {
+            let (letter, octave) = letter_octave_from_semitones(step);
+            assert_eq!(semitones_from_letter_octave(letter, octave), step);
+        }
+    }
+
+    #[test]
+    fn frequency_range() {
+        let piano = FrequencyRange::new(27.5, 4186.0).unwrap();
+        assert_eq!(perc_from_hz_in_range(27.5, piano), 0.0);
+        assert_eq!(perc_from_hz_in_range(4186.0, piano), 1.0);
+        assert_eq!(hz_from_perc_in_range(0.5, piano), 2106.75);
+        let scaled = scaled_perc_from_hz_in_range(440.0, 4.0, piano);
+        assert!((hz_from_scaled_perc_in_range(scaled, 4.0, piano) - 440.0).abs() < 1e-3);
+        assert_eq!(
+            perc_from_hz(440.0),
+            perc_from_hz_in_range(440.0, Default::default())
+        );
+        assert_eq!(
+            HzType(440.0).to_perc_in_range(piano).to_hz_in_range(piano),
+            HzType(440.0)
+        );
+
+        assert_eq!(
+            FrequencyRange::new(4186.0, 27.5),
+            Err(PitchError::InvalidRange(4186.0, 27.5))
+        );
+        assert!(FrequencyRange::new(0.0, 100.0).is_err());
+        assert!(FrequencyRange::new(100.0, 100.0).is_err());
+        assert!(FrequencyRange::new(20.0, Hz::INFINITY).is_err());
+        assert!(piano.contains(HzType(440.0)));
+        assert_eq!(piano.clamp(HzType(10.0)), HzType(27.5));
+    }
+}
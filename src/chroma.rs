@@ -0,0 +1,338 @@
+//! Folding spectra into pitch classes.
+//!
+//! A `Chroma` describes how the energy of a magnitude spectrum is distributed among the pitch
+//! classes, independent of octave. The classes are keyed by `Letter`, starting from `C`.
+
+use crate::{
+    calc, hz_from_step_with_tuning, step_from_hz_with_tuning, BinMapper, Hz, Letter, PitchError,
+    DEFAULT_TUNING, TOTAL_LETTERS,
+};
+use std::f32::consts::PI;
+
+/// How the pitch class energies of a `Chroma` are scaled.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ChromaNorm {
+    /// The energies are left as summed from the spectrum.
+    None,
+    /// The energies are divided by the largest, so that the strongest class is `1.0`.
+    Max,
+    /// The energies are divided by their sum.
+    Sum,
+    /// The energies are divided by their euclidean length.
+    Euclidean,
+}
+
+/// The parameters used by `chroma` when folding a spectrum.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ChromaOptions {
+    /// The number of pitch classes per octave, e.g. `12` for one per semitone or `36` for three.
+    pub classes: usize,
+    /// The deviation of the spectrum from standard tuning in cents, or `None` to estimate it.
+    pub tuning: Option<f32>,
+    /// The number of harmonics of each pitch class to accumulate. `1` disables harmonic weighting.
+    pub harmonics: usize,
+    /// The weight of each successive harmonic relative to the previous.
+    pub harmonic_decay: f32,
+    /// The lowest frequency considered.
+    pub min: Hz,
+    /// The highest frequency considered.
+    pub max: Hz,
+    /// How the pitch class energies are scaled.
+    pub norm: ChromaNorm,
+}
+
+/// The energy of each pitch class within a spectrum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chroma {
+    /// The energy of each class, spaced evenly across the octave starting from `C`.
+    pub energies: Vec<f32>,
+    /// The tuning deviation in cents relative to which the spectrum was folded.
+    pub tuning: f32,
+}
+
+impl Default for ChromaOptions {
+    fn default() -> ChromaOptions {
+        ChromaOptions {
+            classes: TOTAL_LETTERS as usize,
+            tuning: None,
+            harmonics: 1,
+            harmonic_decay: 0.6,
+            min: Hz(55.0),
+            max: Hz(5_000.0),
+            norm: ChromaNorm::Max,
+        }
+    }
+}
+
+impl Chroma {
+    /// The pitch class of the letter nearest to the centre of the class at the given index.
+    #[inline]
+    fn pitch_class(&self, index: usize) -> u8 {
+        let semitones = (index * TOTAL_LETTERS as usize) as f64 / self.energies.len() as f64;
+        Letter::from_pitch_class(semitones.round() as i64).pitch_class()
+    }
+
+    /// The total energy of the classes nearest to the given letter.
+    ///
+    /// With fewer than twelve classes some letters have none, and so no energy.
+    pub fn letter(&self, letter: Letter) -> f32 {
+        let pitch_class = letter.pitch_class();
+        self.energies
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| self.pitch_class(index) == pitch_class)
+            .map(|(_, energy)| energy)
+            .sum()
+    }
+
+    /// The letter with the greatest energy.
+    pub fn strongest(&self) -> Letter {
+        self.letters()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(letter, _)| letter)
+            .unwrap_or(Letter::C)
+    }
+
+    /// Iterate over the energy of each of the twelve letters, starting from `C`.
+    pub fn letters(&self) -> impl Iterator<Item = (Letter, f32)> + '_ {
+        (0..TOTAL_LETTERS as i64).map(move |pc| {
+            let letter = Letter::from_pitch_class(pc);
+            (letter, self.letter(letter))
+        })
+    }
+}
+
+/// The BinMapper for a spectrum of the given length produced by a real FFT.
+///
+/// A spectrum with fewer than two bins is treated as that of a single sample, whose only bin is
+/// 0hz.
+fn mapper(spectrum: &[f32], sample_rate: calc::Hz) -> Result<BinMapper, PitchError> {
+    BinMapper::new(sample_rate, (spectrum.len().saturating_sub(1) * 2).max(1))
+}
+
+/// Estimate the deviation of the spectrum from standard tuning in cents (within -50..50).
+///
+/// The frequency of each spectral peak between `min` and `max` is refined by interpolation and its
+/// distance from the nearest equal tempered semitone is averaged, weighted by magnitude.
+///
+/// Returns an error if the sample rate is not finite and positive.
+pub fn estimate_tuning(
+    spectrum: &[f32],
+    sample_rate: calc::Hz,
+    min: Hz,
+    max: Hz,
+) -> Result<f32, PitchError> {
+    let mapper = mapper(spectrum, sample_rate)?;
+    let log: Vec<f32> = spectrum.iter().map(|m| m.max(1e-12).ln()).collect();
+    let (mut x, mut y) = (0.0, 0.0);
+    for bin in 1..spectrum.len().saturating_sub(1) {
+        let magnitude = spectrum[bin];
+        let hz = mapper.bin_to_hz(bin as f32);
+        if hz < min || hz > max || magnitude <= spectrum[bin - 1] || magnitude < spectrum[bin + 1] {
+            continue;
+        }
+        let step = mapper.interpolate_peak(&log, bin).step();
+        // Average on the circle so that deviations of -49 and +49 cents don't cancel out.
+        let angle = (step - step.round()) * 2.0 * PI;
+        x += magnitude * angle.cos();
+        y += magnitude * angle.sin();
+    }
+    if x == 0.0 && y == 0.0 {
+        Ok(0.0)
+    } else {
+        Ok(y.atan2(x) / (2.0 * PI) * 100.0)
+    }
+}
+
+/// Fold the magnitude spectrum of a real FFT (from 0hz to the nyquist frequency inclusive) into
+/// pitch classes.
+///
+/// Each frequency is credited to the class nearest to its step scaled by `classes / 12`, modulo
+/// `classes`. With no classes the chroma is empty.
+///
+/// Returns an error if the sample rate is not finite and positive.
+pub fn chroma(
+    spectrum: &[f32],
+    sample_rate: calc::Hz,
+    options: ChromaOptions,
+) -> Result<Chroma, PitchError> {
+    let mapper = mapper(spectrum, sample_rate)?;
+    let tuning = match options.tuning {
+        Some(tuning) => tuning,
+        None => estimate_tuning(spectrum, sample_rate, options.min, options.max)?,
+    };
+    let a4 = hz_from_step_with_tuning(69.0 + tuning / 100.0, DEFAULT_TUNING);
+    if options.classes == 0 {
+        return Ok(Chroma {
+            energies: Vec::new(),
+            tuning,
+        });
+    }
+    let classes_per_step = options.classes as f32 / TOTAL_LETTERS as f32;
+
+    let mut energies = vec![0.0; options.classes];
+    for (bin, &magnitude) in spectrum.iter().enumerate().skip(1) {
+        let hz = mapper.bin_to_hz(bin as f32);
+        if hz < options.min || hz > options.max {
+            continue;
+        }
+        // Credit the energy to the pitch class of every fundamental of which it may be a harmonic.
+        let mut weight = 1.0;
+        for harmonic in 1..=options.harmonics.max(1) {
+            let step = step_from_hz_with_tuning(hz.hz() / harmonic as f32, a4);
+            let class = (step * classes_per_step).round() as i64;
+            energies[class.rem_euclid(options.classes as i64) as usize] += magnitude * weight;
+            weight *= options.harmonic_decay;
+        }
+    }
+
+    let scale = match options.norm {
+        ChromaNorm::None => 1.0,
+        ChromaNorm::Max => energies.iter().cloned().fold(0.0, f32::max),
+        ChromaNorm::Sum => energies.iter().sum(),
+        ChromaNorm::Euclidean => energies.iter().map(|e| e * e).sum::<f32>().sqrt(),
+    };
+    if scale > 0.0 {
+        energies.iter_mut().for_each(|e| *e /= scale);
+    }
+
+    Ok(Chroma { energies, tuning })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Step;
+
+    const SAMPLE_RATE: f32 = 22_050.0;
+    const BINS: usize = 4097;
+
+    /// A spectrum with gaussian peaks at each of the given frequencies.
+    fn spectrum(peaks: &[(f32, f32)]) -> Vec<f32> {
+        let mapper = BinMapper::new(SAMPLE_RATE, (BINS - 1) * 2).unwrap();
+        (0..BINS)
+            .map(|bin| {
+                peaks
+                    .iter()
+                    .map(|&(hz, gain)| {
+                        let distance = bin as f32 - mapper.hz_to_bin(Hz(hz));
+                        gain * (-distance * distance).exp()
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn folds_to_letters() {
+        // An A major triad.
+        let notes = [Step(57.0), Step(61.0), Step(64.0), Step(69.0)];
+        let peaks: Vec<_> = notes.iter().map(|s| (s.hz(), 1.0)).collect();
+        let options = ChromaOptions {
+            tuning: Some(0.0),
+            ..Default::default()
+        };
+        let chroma = chroma(&spectrum(&peaks), SAMPLE_RATE, options).unwrap();
+        assert_eq!(chroma.strongest(), Letter::A);
+        assert!((chroma.letter(Letter::A) - 1.0).abs() < 1e-3);
+        assert!((chroma.letter(Letter::Csh) - 0.5).abs() < 1e-3);
+        assert!(chroma.letter(Letter::D) < 1e-3);
+    }
+
+    #[test]
+    fn arbitrary_classes() {
+        // An A major triad.
+        let notes = [Step(57.0), Step(61.0), Step(64.0), Step(69.0)];
+        let peaks: Vec<_> = notes.iter().map(|s| (s.hz(), 1.0)).collect();
+        let fold = |classes| {
+            let options = ChromaOptions {
+                classes,
+                tuning: Some(0.0),
+                norm: ChromaNorm::None,
+                ..Default::default()
+            };
+            chroma(&spectrum(&peaks), SAMPLE_RATE, options).unwrap()
+        };
+
+        let twelve = fold(12);
+        let quarter_tones = fold(24);
+        assert_eq!(quarter_tones.energies.len(), 24);
+        assert_eq!(quarter_tones.strongest(), Letter::A);
+        assert!(quarter_tones.letter(Letter::D) < 0.01 * quarter_tones.letter(Letter::A));
+
+        // The classes lie 12/7 semitones apart, so both As fall in the class nearest to A.
+        let seven = fold(7);
+        assert_eq!(seven.energies.len(), 7);
+        assert_eq!(seven.strongest(), Letter::A);
+        assert!((seven.letter(Letter::A) - twelve.letter(Letter::A)).abs() < 1e-2);
+        let total: f32 = seven.letters().map(|(_, energy)| energy).sum();
+        assert!((total - seven.energies.iter().sum::<f32>()).abs() < 1e-3);
+
+        assert!(fold(0).energies.is_empty());
+        assert_eq!(fold(0).letter(Letter::A), 0.0);
+    }
+
+    #[test]
+    fn harmonic_weighting() {
+        // A lone G that may be the third harmonic of a C.
+        let peaks = [(Step(67.0).hz(), 1.0)];
+        let options = ChromaOptions {
+            tuning: Some(0.0),
+            harmonics: 3,
+            norm: ChromaNorm::None,
+            ..Default::default()
+        };
+        let harmonic = chroma(&spectrum(&peaks), SAMPLE_RATE, options).unwrap();
+        let plain = chroma(
+            &spectrum(&peaks),
+            SAMPLE_RATE,
+            ChromaOptions {
+                harmonics: 1,
+                ..options
+            },
+        )
+        .unwrap();
+        assert!(plain.letter(Letter::C) < 1e-3);
+        let ratio = harmonic.letter(Letter::C) / harmonic.letter(Letter::G);
+        // The third harmonic is weighted by `decay^2`, the second (also a G) by `decay`.
+        assert!((ratio - 0.36 / 1.6).abs() < 1e-3, "{}", ratio);
+        assert_eq!(harmonic.strongest(), Letter::G);
+    }
+
+    #[test]
+    fn tuning_estimation() {
+        // Every note of a C major scale, 20 cents sharp.
+        let peaks: Vec<_> = [60.0, 62.0, 64.0, 65.0, 67.0, 69.0, 71.0]
+            .iter()
+            .map(|&step| (Step(step + 0.2).hz(), 1.0))
+            .collect();
+        let spectrum = spectrum(&peaks);
+        let tuning = estimate_tuning(&spectrum, SAMPLE_RATE, Hz(55.0), Hz(5_000.0)).unwrap();
+        assert!((tuning - 20.0).abs() < 0.5, "{}", tuning);
+        let chroma = chroma(
+            &spectrum,
+            SAMPLE_RATE,
+            ChromaOptions {
+                classes: 36,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!((chroma.tuning - tuning).abs() < 1e-3);
+        assert!(chroma.letter(Letter::C) > 10.0 * chroma.letter(Letter::Csh));
+    }
+
+    #[test]
+    fn degenerate_spectra() {
+        let options = ChromaOptions::default();
+        for spectrum in [&[][..], &[1.0]] {
+            let chroma = chroma(spectrum, SAMPLE_RATE, options).unwrap();
+            assert_eq!(chroma.tuning, 0.0);
+            assert!(chroma.energies.iter().all(|&energy| energy == 0.0));
+        }
+        assert_eq!(
+            chroma(&[1.0; BINS], 0.0, options),
+            Err(PitchError::NonPositiveFrequency(0.0))
+        );
+    }
+}
@@ -0,0 +1,371 @@
+//! Contour and similarity analysis of melodies, e.g. for query-by-humming.
+//!
+//! Every function accepts a sequence of any `Pitch`, compared by its `Step`, so that both notated
+//! melodies (`LetterOctave`) and pitch curves tracked from audio (`Step` or `Hz`) may be analysed
+//! and compared with one another.
+
+use crate::{calc, Interval, Pitch, Step};
+use std::collections::BTreeMap;
+
+/// Differences in step smaller than this many semitones are considered repetitions by `contour`.
+pub const DEFAULT_CONTOUR_THRESHOLD: calc::Step = 0.5;
+
+/// The direction of the melody from one note to the next.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Repeat,
+}
+
+/// The lowest and highest pitches of a melody or part of one.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ambitus {
+    pub lowest: Step,
+    pub highest: Step,
+}
+
+impl Direction {
+    /// The letter representing the direction in the Parsons code: `U`, `D` or `R`.
+    pub fn parsons(self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Repeat => 'R',
+        }
+    }
+}
+
+impl Ambitus {
+    /// The distance from the lowest to the highest pitch in semitones.
+    #[inline]
+    pub fn semitones(self) -> calc::Step {
+        self.highest.step() - self.lowest.step()
+    }
+
+    /// The distance from the lowest to the highest pitch, rounded to the nearest interval.
+    #[inline]
+    pub fn interval(self) -> Interval {
+        Interval(self.semitones().round() as calc::Semitones)
+    }
+}
+
+/// The step of each pitch.
+fn steps<P: Pitch>(pitches: &[P]) -> Vec<calc::Step> {
+    pitches.iter().map(|p| p.to_step().step()).collect()
+}
+
+/// The direction between each successive pair of pitches.
+pub fn contour<P: Pitch>(pitches: &[P]) -> Vec<Direction> {
+    contour_with_threshold(pitches, DEFAULT_CONTOUR_THRESHOLD)
+}
+
+/// The direction between each successive pair of pitches, where differences smaller than
+/// `threshold` semitones are repetitions.
+pub fn contour_with_threshold<P: Pitch>(pitches: &[P], threshold: calc::Step) -> Vec<Direction> {
+    steps(pitches)
+        .windows(2)
+        .map(|pair| match pair[1] - pair[0] {
+            d if d >= threshold => Direction::Up,
+            d if d <= -threshold => Direction::Down,
+            _ => Direction::Repeat,
+        })
+        .collect()
+}
+
+/// The Parsons code of the melody, e.g. `"*UURD"`, where `*` marks the first note.
+///
+/// Returns an empty string for an empty melody.
+pub fn parsons_code<P: Pitch>(pitches: &[P]) -> String {
+    if pitches.is_empty() {
+        return String::new();
+    }
+    let directions = contour(pitches).into_iter().map(Direction::parsons);
+    std::iter::once('*').chain(directions).collect()
+}
+
+/// The interval between each successive pair of pitches, rounded to the nearest semitone.
+pub fn intervals<P: Pitch>(pitches: &[P]) -> Vec<Interval> {
+    steps(pitches)
+        .windows(2)
+        .map(|pair| Interval((pair[1] - pair[0]).round() as calc::Semitones))
+        .collect()
+}
+
+/// The number of times each interval occurs between successive pitches.
+pub fn interval_histogram<P: Pitch>(pitches: &[P]) -> BTreeMap<Interval, usize> {
+    let mut histogram = BTreeMap::new();
+    for interval in intervals(pitches) {
+        *histogram.entry(interval).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// The lowest and highest pitches of the melody, or `None` if it is empty.
+pub fn ambitus<P: Pitch>(pitches: &[P]) -> Option<Ambitus> {
+    let steps = steps(pitches);
+    let lowest = steps.iter().copied().min_by(f32::total_cmp)?;
+    let highest = steps.iter().copied().max_by(f32::total_cmp)?;
+    Some(Ambitus {
+        lowest: Step(lowest),
+        highest: Step(highest),
+    })
+}
+
+/// The range spanned by the central `proportion` of the melody's pitches, e.g. `0.8` to exclude
+/// the lowest and highest tenth, or `None` if it is empty.
+///
+/// Each pitch counts once per occurrence, so the tessitura reflects where the melody lies most of
+/// the time rather than its extremes.
+pub fn tessitura<P: Pitch>(pitches: &[P], proportion: f32) -> Option<Ambitus> {
+    let mut steps = steps(pitches);
+    if steps.is_empty() {
+        return None;
+    }
+    steps.sort_by(f32::total_cmp);
+    let outside = (1.0 - proportion.clamp(0.0, 1.0)) / 2.0;
+    let last = steps.len() - 1;
+    let rank = |quantile: f32| (quantile * last as f32).round() as usize;
+    Some(Ambitus {
+        lowest: Step(steps[rank(outside)]),
+        highest: Step(steps[rank(1.0 - outside)]),
+    })
+}
+
+/// The Levenshtein distance between two sequences.
+fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(x != y);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// The number of intervals that must be inserted, deleted or substituted to turn the intervals of
+/// one melody into those of the other.
+///
+/// As only intervals are compared, the distance is unaffected by transposition.
+pub fn interval_edit_distance<P: Pitch, Q: Pitch>(a: &[P], b: &[Q]) -> usize {
+    edit_distance(&intervals(a), &intervals(b))
+}
+
+/// The number of directions that must be inserted, deleted or substituted to turn the contour of
+/// one melody into that of the other.
+pub fn contour_edit_distance<P: Pitch, Q: Pitch>(a: &[P], b: &[Q]) -> usize {
+    edit_distance(&contour(a), &contour(b))
+}
+
+/// The dynamic time warping of two pitch curves, with the cost of aligning two pitches given by
+/// their distance in semitones.
+///
+/// Returns the total cost and the aligned pairs of indices, or an infinite cost if only one of the
+/// curves is empty.
+pub(crate) fn dtw(a: &[calc::Step], b: &[calc::Step]) -> (f32, Vec<(usize, usize)>) {
+    if a.is_empty() || b.is_empty() {
+        let cost = if a.len() == b.len() {
+            0.0
+        } else {
+            f32::INFINITY
+        };
+        return (cost, Vec::new());
+    }
+    let width = b.len() + 1;
+    let mut cost = vec![f32::INFINITY; (a.len() + 1) * width];
+    cost[0] = 0.0;
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let best = cost[(i - 1) * width + j - 1]
+                .min(cost[(i - 1) * width + j])
+                .min(cost[i * width + j - 1]);
+            cost[i * width + j] = (a[i - 1] - b[j - 1]).abs() + best;
+        }
+    }
+
+    let mut path = vec![(a.len() - 1, b.len() - 1)];
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 1 || j > 1 {
+        let steps = [(i - 1, j - 1), (i - 1, j), (i, j - 1)];
+        (i, j) = steps
+            .into_iter()
+            .filter(|&(i, j)| i > 0 && j > 0)
+            .min_by(|&(a, b), &(c, d)| cost[a * width + b].total_cmp(&cost[c * width + d]))
+            .unwrap();
+        path.push((i - 1, j - 1));
+    }
+    path.reverse();
+    (cost[a.len() * width + b.len()], path)
+}
+
+/// The dynamic time warping distance between two pitch curves in semitones, tolerant of
+/// differences in tempo and note length.
+///
+/// The distance is zero if and only if the curves are identical up to the repetition of pitches.
+/// Returns infinity if only one of the curves is empty.
+pub fn dtw_distance<P: Pitch, Q: Pitch>(a: &[P], b: &[Q]) -> f32 {
+    dtw(&steps(a), &steps(b)).0
+}
+
+/// The dynamic time warping distance between two pitch curves after transposing `query` to best
+/// match `target`, along with the transposition in semitones.
+///
+/// The transposition is refined by alternately aligning the curves and moving the query by the
+/// median difference between its aligned pitches and those of the target, so that a query hummed
+/// in any key may be matched.
+pub fn dtw_distance_transposed<P: Pitch, Q: Pitch>(query: &[P], target: &[Q]) -> (f32, f32) {
+    let query = steps(query);
+    let target = steps(target);
+    let mut transposition = median(&target) - median(&query);
+    let mut best = (f32::INFINITY, transposition);
+    for _ in 0..8 {
+        let transposed: Vec<calc::Step> = query.iter().map(|s| s + transposition).collect();
+        let (cost, path) = dtw(&transposed, &target);
+        if cost >= best.0 {
+            break;
+        }
+        best = (cost, transposition);
+        let differences: Vec<calc::Step> =
+            path.iter().map(|&(i, j)| target[j] - query[i]).collect();
+        transposition = median(&differences);
+    }
+    if best.0.is_infinite() {
+        best.1 = 0.0;
+    }
+    best
+}
+
+/// The median of the values, or `0.0` if there are none.
+fn median(values: &[f32]) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2.0
+    } else {
+        sorted[middle]
+    }
+}
+
+/// Every occurrence of `query` within `melody` in any transposition.
+///
+/// Returns the index in `melody` at which each occurrence begins, along with the interval by which
+/// the query is transposed there. Pitches are compared by their intervals, rounded to the nearest
+/// semitone.
+pub fn find_transposed<P: Pitch, Q: Pitch>(query: &[P], melody: &[Q]) -> Vec<(usize, Interval)> {
+    if query.is_empty() || query.len() > melody.len() {
+        return Vec::new();
+    }
+    let pattern = intervals(query);
+    let melody_intervals = intervals(melody);
+    let first = query[0].to_step().step();
+    (0..=melody.len() - query.len())
+        .filter(|&start| melody_intervals[start..start + pattern.len()] == pattern[..])
+        .map(|start| {
+            let shift = melody[start].to_step().step() - first;
+            (start, Interval(shift.round() as calc::Semitones))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hz, Letter, LetterOctave};
+
+    /// The opening of "Twinkle, Twinkle, Little Star" on the given tonic.
+    fn twinkle(tonic: LetterOctave) -> Vec<LetterOctave> {
+        [0, 0, 7, 7, 9, 9, 7, 5, 5, 4, 4, 2, 2, 0]
+            .iter()
+            .map(|&s| tonic + Interval(s))
+            .collect()
+    }
+
+    #[test]
+    fn contour_and_intervals() {
+        let melody = twinkle(LetterOctave(Letter::C, 4));
+        assert_eq!(parsons_code(&melody), "*RURURDDRDRDRD");
+        assert_eq!(parsons_code::<Step>(&[]), "");
+        let hummed = [Step(60.0), Step(60.2), Step(62.1), Step(61.4)];
+        assert_eq!(
+            contour(&hummed),
+            vec![Direction::Repeat, Direction::Up, Direction::Down]
+        );
+        let histogram = interval_histogram(&melody);
+        assert_eq!(histogram[&Interval::UNISON], 6);
+        assert_eq!(histogram[&Interval(-2)], 4);
+        assert_eq!(histogram[&Interval::PERFECT_FIFTH], 1);
+        assert_eq!(histogram.values().sum::<usize>(), melody.len() - 1);
+    }
+
+    #[test]
+    fn range_and_tessitura() {
+        let melody = twinkle(LetterOctave(Letter::C, 4));
+        let ambitus = ambitus(&melody).unwrap();
+        assert_eq!(ambitus.lowest, Step(60.0));
+        assert_eq!(ambitus.interval(), Interval::MAJOR_SIXTH);
+        let mut outlier = melody.clone();
+        outlier.push(LetterOctave(Letter::C, 6));
+        let tessitura = tessitura(&outlier, 0.8).unwrap();
+        assert_eq!(tessitura.highest, Step(69.0));
+        assert!(super::ambitus::<Hz>(&[]).is_none());
+    }
+
+    #[test]
+    fn edit_distances() {
+        let c = twinkle(LetterOctave(Letter::C, 4));
+        let g = twinkle(LetterOctave(Letter::G, 3));
+        assert_eq!(interval_edit_distance(&c, &g), 0);
+        let mut varied = c.clone();
+        varied[4] = LetterOctave(Letter::B, 4);
+        varied.remove(1);
+        assert_eq!(interval_edit_distance(&c, &varied), 3);
+        assert_eq!(contour_edit_distance(&c, &varied), 2);
+        assert_eq!(edit_distance(b"kitten", b"sitting"), 3);
+    }
+
+    #[test]
+    fn dynamic_time_warping() {
+        let melody = twinkle(LetterOctave(Letter::C, 4));
+        // Sung slowly, with each note held for two frames.
+        let slow: Vec<Step> = melody
+            .iter()
+            .flat_map(|n| [Step(n.step()), Step(n.step())])
+            .collect();
+        assert!(dtw_distance(&melody, &slow).abs() < 1e-4);
+        assert!(dtw_distance(&melody, &slow[..20]) > 1.0);
+        assert_eq!(
+            dtw_distance::<Step, Step>(&[], &[Step(60.0)]),
+            f32::INFINITY
+        );
+
+        // Sung a fourth higher and slightly flat.
+        let sung: Vec<Step> = slow.iter().map(|s| Step(s.step() + 4.8)).collect();
+        assert!(dtw_distance(&sung, &melody) > 10.0);
+        let (distance, transposition) = dtw_distance_transposed(&sung, &melody);
+        assert!(distance < 1e-3, "{}", distance);
+        assert!((transposition + 4.8).abs() < 1e-4, "{}", transposition);
+    }
+
+    #[test]
+    fn transposition_invariant_matching() {
+        let melody = twinkle(LetterOctave(Letter::C, 4));
+        let query = [
+            LetterOctave(Letter::D, 5),
+            LetterOctave(Letter::D, 5),
+            LetterOctave(Letter::C, 5),
+        ];
+        assert_eq!(
+            find_transposed(&query, &melody),
+            vec![(4, Interval(-5)), (9, Interval(-10)), (11, Interval(-12))]
+        );
+        assert!(find_transposed(&query, &melody[..3]).is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Hz, LetterOctave, Mel, Perc, ScaledPerc, Step};
+use super::{Bark, Erb, Hz, LetterOctave, Mel, Perc, Pitch, ScaledPerc, Step};
 use crate::calc::Hz as CalcHz;
 use std::convert::From;
 
@@ -13,7 +13,7 @@ macro_rules! impl_from {
     };
 
     ($FromType:ty, $ToType:ty, $member:ident) => {
-        impl_from!($FromType, $ToType, other => other.$member());
+        impl_from!($FromType, $ToType, other => Pitch::$member(other));
     };
 }
 
@@ -36,6 +36,8 @@ macro_rules! impl_all_pairs {
 impl_all_pairs!(
 to_hz => Hz,
 to_mel => Mel,
+to_bark => Bark,
+to_erb => Erb,
 to_letter_octave => LetterOctave,
 to_scaled_perc => ScaledPerc,
 to_perc => Perc,
@@ -60,6 +62,14 @@ mod tests {
         assert!(Hz::from(lo) == Hz(440.0));
     }
 
+    #[test]
+    fn psychoacoustic_conversion() {
+        let hz = Hz(1000.0);
+        assert!((Bark::from(hz).bark() - 8.527).abs() < 1e-3);
+        assert!((Hz::from(Erb::from(hz)).hz() - 1000.0).abs() < 1e-2);
+        assert_eq!(LetterOctave::from(Bark::from(hz)), LetterOctave::from(hz));
+    }
+
     #[test]
     fn function_call() {
         let lo = LetterOctave(Letter::A, 4);
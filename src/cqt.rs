@@ -0,0 +1,140 @@
+//! Frequency grids for constant-Q transforms and chromagrams.
+//!
+//! A `ConstantQ` describes a set of bins with centre frequencies spaced geometrically at a fixed
+//! number of bins per octave, each with the same ratio of centre frequency to bandwidth (`Q`).
+
+use crate::{
+    calc, hz_from_step_with_tuning, step_from_hz_with_tuning, Hz, LetterOctave, Step,
+    DEFAULT_TUNING,
+};
+
+/// A grid of constant-Q bins starting at a given note.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConstantQ {
+    /// The sample rate of the analysed signal in hz.
+    pub sample_rate: calc::Hz,
+    /// The note at the centre of the lowest bin.
+    pub min: LetterOctave,
+    /// The total number of bins.
+    pub bins: usize,
+    /// The number of bins in each octave, e.g. `12` for semitone spacing.
+    pub bins_per_octave: u32,
+    /// The frequency of A4 in hz to which the grid is tuned.
+    pub tuning: calc::Hz,
+    /// Scales the length of every window, e.g. `0.5` halves the `Q` of every bin.
+    pub filter_scale: f32,
+}
+
+/// The centre, bandwidth and window length of a single constant-Q bin.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CqtBin {
+    /// The pitch at the centre of the bin, relative to the grid's tuning.
+    pub step: Step,
+    /// The centre frequency of the bin.
+    pub hz: Hz,
+    /// The ratio of the centre frequency to the bandwidth.
+    pub q: f32,
+    /// The bandwidth of the bin.
+    pub bandwidth: Hz,
+    /// The length in samples of the window required to resolve the bin.
+    pub window_length: f32,
+}
+
+impl ConstantQ {
+    /// A constructor for a ConstantQ in standard tuning.
+    pub fn new(
+        sample_rate: calc::Hz,
+        min: LetterOctave,
+        bins: usize,
+        bins_per_octave: u32,
+    ) -> ConstantQ {
+        ConstantQ {
+            sample_rate,
+            min,
+            bins,
+            bins_per_octave,
+            tuning: DEFAULT_TUNING,
+            filter_scale: 1.0,
+        }
+    }
+
+    /// The same grid tuned so that A4 has the frequency `a4`.
+    pub fn with_tuning(self, a4: Hz) -> ConstantQ {
+        ConstantQ {
+            tuning: a4.hz(),
+            ..self
+        }
+    }
+
+    /// The same grid retuned by the given deviation from standard tuning in cents, e.g. a tuning
+    /// offset estimated from the analysed signal.
+    pub fn with_tuning_deviation(self, cents: f32) -> ConstantQ {
+        self.with_tuning(Hz(DEFAULT_TUNING * (cents / 1200.0).exp2()))
+    }
+
+    /// The ratio of centre frequency to bandwidth shared by every bin.
+    #[inline]
+    pub fn q(&self) -> f32 {
+        self.filter_scale / ((1.0 / self.bins_per_octave as f32).exp2() - 1.0)
+    }
+
+    /// The bin at the given index, which may lie beyond `bins`.
+    pub fn bin(&self, index: usize) -> CqtBin {
+        let step = self.min.step() + index as calc::Step * 12.0 / self.bins_per_octave as f32;
+        let hz = hz_from_step_with_tuning(step, self.tuning);
+        let q = self.q();
+        CqtBin {
+            step: Step(step),
+            hz: Hz(hz),
+            q,
+            bandwidth: Hz(hz / q),
+            window_length: q * self.sample_rate / hz,
+        }
+    }
+
+    /// The (possibly fractional) index of the bin centred on the given frequency.
+    pub fn bin_index(&self, hz: Hz) -> f32 {
+        let step = step_from_hz_with_tuning(hz.hz(), self.tuning);
+        (step - self.min.step()) * self.bins_per_octave as f32 / 12.0
+    }
+
+    /// Iterate over every bin in order of ascending frequency.
+    pub fn iter(&self) -> impl Iterator<Item = CqtBin> + '_ {
+        (0..self.bins).map(move |index| self.bin(index))
+    }
+
+    /// Iterate over the centre pitch of every bin as both a `Step` and `Hz`.
+    pub fn steps(&self) -> impl Iterator<Item = (Step, Hz)> + '_ {
+        self.iter().map(|bin| (bin.step, bin.hz))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Letter;
+
+    #[test]
+    fn semitone_grid() {
+        let cqt = ConstantQ::new(22_050.0, LetterOctave(Letter::A, 0), 88, 12);
+        let (step, hz) = cqt.steps().nth(48).unwrap();
+        assert_eq!(step, Step(69.0));
+        assert_eq!(hz, Hz(440.0));
+        assert!((cqt.q() - 16.817).abs() < 1e-3);
+        let bin = cqt.bin(48);
+        assert!((bin.window_length - 16.817 * 22_050.0 / 440.0).abs() < 0.1);
+        assert!((bin.bandwidth.hz() - 440.0 / 16.817).abs() < 1e-2);
+        assert!((cqt.bin_index(Hz(880.0)) - 60.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn tuned_grid() {
+        let cqt =
+            ConstantQ::new(22_050.0, LetterOctave(Letter::C, 1), 252, 36).with_tuning(Hz(442.0));
+        let a4 = cqt.bin(36 * 3 + 27);
+        assert_eq!(a4.step, Step(69.0));
+        assert!((a4.hz.hz() - 442.0).abs() < 1e-3);
+        let detuned = cqt.with_tuning_deviation(-50.0).bin(36 * 3 + 27);
+        assert!((detuned.hz.hz() - 427.474).abs() < 1e-2);
+    }
+}
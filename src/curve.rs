@@ -0,0 +1,315 @@
+//! Invertible curves mapping between a linear `Perc` and a `ScaledPerc`.
+//!
+//! A curve's `scale` maps a linear percentage of the frequency range onto the scaled percentage,
+//! e.g. the position of a knob, and `unscale` maps it back. Both map `0.0` to `0.0` and `1.0` to
+//! `1.0`.
+
+use crate::{
+    calc, perc_from_hz_in_range, FrequencyRange, Hz, PitchError, DEFAULT_FREQUENCY_RANGE,
+    DEFAULT_SCALE_WEIGHT,
+};
+use std::fmt;
+
+/// An invertible mapping between linear and scaled percentages.
+pub trait Curve {
+    /// Map a linear percentage to a scaled percentage.
+    fn scale(&self, perc: calc::Perc) -> calc::Perc;
+    /// Map a scaled percentage back to a linear percentage.
+    fn unscale(&self, scaled: calc::Perc) -> calc::Perc;
+
+    /// Check that the parameters of the curve are valid, e.g. when deserialized.
+    #[inline]
+    fn validate(&self) -> Result<(), PitchError> {
+        Ok(())
+    }
+}
+
+/// A curve with a conventional default, used when a `ScaledPerc` is converted from another pitch
+/// by `Pitch::from_hz`.
+///
+/// This is not `Default`, as the default `0.0` is not a valid weight.
+pub trait DefaultCurve: Curve {
+    /// The curve along which converted percentages are scaled.
+    fn default_curve() -> Self;
+}
+
+/// The original power curve, where the weight `w` maps `perc` to `perc^(1/w)`.
+impl Curve for calc::Weight {
+    #[inline]
+    fn scale(&self, perc: calc::Perc) -> calc::Perc {
+        perc.powf(1.0 / calc::Perc::from(*self))
+    }
+
+    #[inline]
+    fn unscale(&self, scaled: calc::Perc) -> calc::Perc {
+        scaled.powf(calc::Perc::from(*self))
+    }
+
+    #[inline]
+    fn validate(&self) -> Result<(), PitchError> {
+        calc::check_weight(*self).map(|_| ())
+    }
+}
+
+/// The `DEFAULT_SCALE_WEIGHT`.
+impl DefaultCurve for calc::Weight {
+    #[inline]
+    fn default_curve() -> calc::Weight {
+        DEFAULT_SCALE_WEIGHT
+    }
+}
+
+impl<C: Curve + ?Sized> Curve for &C {
+    #[inline]
+    fn scale(&self, perc: calc::Perc) -> calc::Perc {
+        (**self).scale(perc)
+    }
+
+    #[inline]
+    fn unscale(&self, scaled: calc::Perc) -> calc::Perc {
+        (**self).unscale(scaled)
+    }
+
+    #[inline]
+    fn validate(&self) -> Result<(), PitchError> {
+        (**self).validate()
+    }
+}
+
+/// A curve along which the scaled percentage is proportional to the logarithm of frequency, so
+/// that equal distances correspond to equal musical intervals.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Logarithmic {
+    /// The ratio between the highest and lowest frequencies of the range.
+    pub ratio: calc::Perc,
+}
+
+/// A curve along which the linear percentage grows exponentially with the scaled percentage.
+///
+/// A positive `steepness` devotes more of the scaled range to low percentages, a negative
+/// `steepness` to high percentages, and `0.0` is linear.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Exponential {
+    pub steepness: calc::Perc,
+}
+
+/// A skewed power curve, as used by JUCE's `NormalisableRange`, mapping `perc` to `perc^factor`.
+///
+/// When `symmetric` the skew is applied outwards from the centre of the range instead.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Skew {
+    pub factor: calc::Perc,
+    pub symmetric: bool,
+}
+
+/// A curve defined by a pair of functions, which must be inverses of one another.
+#[derive(Copy, Clone)]
+pub struct Custom<F, G> {
+    scale: F,
+    unscale: G,
+}
+
+impl Logarithmic {
+    /// The logarithmic curve for percentages measured over the given frequency range.
+    pub fn for_range(range: FrequencyRange) -> Logarithmic {
+        Logarithmic {
+            ratio: calc::Perc::from(range.max()) / calc::Perc::from(range.min()),
+        }
+    }
+
+    /// The exponential curve with the same shape.
+    #[inline]
+    fn exponential(self) -> Exponential {
+        Exponential {
+            steepness: self.ratio.ln(),
+        }
+    }
+}
+
+impl Default for Logarithmic {
+    fn default() -> Logarithmic {
+        Logarithmic::for_range(DEFAULT_FREQUENCY_RANGE)
+    }
+}
+
+impl Curve for Logarithmic {
+    #[inline]
+    fn scale(&self, perc: calc::Perc) -> calc::Perc {
+        self.exponential().scale(perc)
+    }
+
+    #[inline]
+    fn unscale(&self, scaled: calc::Perc) -> calc::Perc {
+        self.exponential().unscale(scaled)
+    }
+}
+
+/// The logarithmic curve over the `DEFAULT_FREQUENCY_RANGE`.
+impl DefaultCurve for Logarithmic {
+    #[inline]
+    fn default_curve() -> Logarithmic {
+        Logarithmic::default()
+    }
+}
+
+impl Curve for Exponential {
+    #[inline]
+    fn scale(&self, perc: calc::Perc) -> calc::Perc {
+        let k = self.steepness;
+        if k == 0.0 {
+            perc
+        } else {
+            (perc * k.exp_m1()).ln_1p() / k
+        }
+    }
+
+    #[inline]
+    fn unscale(&self, scaled: calc::Perc) -> calc::Perc {
+        let k = self.steepness;
+        if k == 0.0 {
+            scaled
+        } else {
+            (scaled * k).exp_m1() / k.exp_m1()
+        }
+    }
+}
+
+impl Skew {
+    /// A skew with the given factor applied from the start of the range.
+    pub fn new(factor: calc::Perc) -> Skew {
+        Skew {
+            factor,
+            symmetric: false,
+        }
+    }
+
+    /// The skew that places the frequency `centre` at the middle of the scaled range.
+    pub fn for_centre(range: FrequencyRange, centre: Hz) -> Skew {
+        let perc = perc_from_hz_in_range(centre.hz(), range);
+        Skew::new(0.5f64.ln() / perc.ln())
+    }
+
+    /// Apply the skew with the given factor, symmetrically about the centre or from the start.
+    #[inline]
+    fn apply(self, perc: calc::Perc, factor: calc::Perc) -> calc::Perc {
+        if self.symmetric {
+            let distance = 2.0 * perc - 1.0;
+            (1.0 + distance.abs().powf(factor).copysign(distance)) / 2.0
+        } else {
+            perc.powf(factor)
+        }
+    }
+}
+
+impl Curve for Skew {
+    #[inline]
+    fn scale(&self, perc: calc::Perc) -> calc::Perc {
+        self.apply(perc, self.factor)
+    }
+
+    #[inline]
+    fn unscale(&self, scaled: calc::Perc) -> calc::Perc {
+        self.apply(scaled, 1.0 / self.factor)
+    }
+}
+
+impl<F, G> Custom<F, G>
+where
+    F: Fn(calc::Perc) -> calc::Perc,
+    G: Fn(calc::Perc) -> calc::Perc,
+{
+    /// A curve from the function `scale` and its inverse `unscale`.
+    pub fn new(scale: F, unscale: G) -> Custom<F, G> {
+        Custom { scale, unscale }
+    }
+}
+
+impl<F, G> Curve for Custom<F, G>
+where
+    F: Fn(calc::Perc) -> calc::Perc,
+    G: Fn(calc::Perc) -> calc::Perc,
+{
+    #[inline]
+    fn scale(&self, perc: calc::Perc) -> calc::Perc {
+        (self.scale)(perc)
+    }
+
+    #[inline]
+    fn unscale(&self, scaled: calc::Perc) -> calc::Perc {
+        (self.unscale)(scaled)
+    }
+}
+
+impl<F, G> fmt::Debug for Custom<F, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Custom").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perc, ScaledPerc};
+
+    fn assert_invertible<C: Curve>(curve: C) {
+        for i in 0..=20 {
+            let perc = i as calc::Perc / 20.0;
+            let scaled = curve.scale(perc);
+            assert!(
+                (curve.unscale(scaled) - perc).abs() < 1e-9,
+                "{} {}",
+                perc,
+                scaled
+            );
+        }
+        assert!(curve.scale(0.0).abs() < 1e-12);
+        assert!((curve.scale(1.0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn curves_are_invertible() {
+        assert_invertible(4.0f32);
+        assert_invertible(Logarithmic::default());
+        assert_invertible(Exponential { steepness: 3.0 });
+        assert_invertible(Exponential { steepness: -3.0 });
+        assert_invertible(Exponential { steepness: 0.0 });
+        assert_invertible(Skew::new(0.3));
+        assert_invertible(Skew {
+            factor: 0.3,
+            symmetric: true,
+        });
+        assert_invertible(Custom::new(|p: f64| p.sqrt(), |s: f64| s * s));
+    }
+
+    #[test]
+    fn logarithmic_is_uniform_in_pitch() {
+        let range = FrequencyRange::new(27.5, 3520.0).unwrap();
+        let curve = Logarithmic::for_range(range);
+        let octave = |hz: f32| curve.scale(perc_from_hz_in_range(hz, range));
+        // 27.5hz..3520hz spans exactly seven octaves.
+        assert!((octave(55.0) - 1.0 / 7.0).abs() < 1e-6);
+        assert!((octave(440.0) - 4.0 / 7.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn skew_for_centre() {
+        let range = FrequencyRange::new(20.0, 20_000.0).unwrap();
+        let curve = Skew::for_centre(range, Hz(1_000.0));
+        let scaled = curve.scale(perc_from_hz_in_range(1_000.0, range));
+        assert!((scaled - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scaled_perc_with_curve() {
+        let curve = Logarithmic::default();
+        let scaled = Hz(440.0).to_scaled_perc_with_curve(curve);
+        assert!((scaled.hz() - 440.0).abs() < 1e-2);
+        assert_eq!(scaled.curve(), &curve);
+        assert!((scaled.perc() - Hz(440.0).perc()).abs() < 1e-9);
+        assert!(scaled < Hz(880.0).to_scaled_perc_with_curve(curve));
+        assert_eq!(
+            ScaledPerc::from(Perc(0.25)),
+            ScaledPerc(0.25f64.powf(0.25), 4.0)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use super::{calc, erb_bandwidth_from_hz, hz_from_erb, Hz};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
+
+/// ERB-rate value representation
+///     - the number of equivalent rectangular bandwidths below a frequency, as described by
+///       Glasberg and Moore in 1990.
+///
+/// Ordered and hashed by the ERB number with `f32::total_cmp`, so NaN sorts last and equals
+/// itself.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde::ErbRepr"))]
+pub struct Erb(pub calc::Erb);
+
+impl Erb {
+    /// Return the unit value of the Erb struct.
+    #[inline]
+    pub fn erb(self) -> calc::Erb {
+        let Erb(erb) = self;
+        erb
+    }
+
+    /// Convert to hz.
+    #[inline]
+    pub fn hz(self) -> calc::Hz {
+        hz_from_erb(self.erb())
+    }
+
+    /// The equivalent rectangular bandwidth of an auditory filter centred on this pitch.
+    #[inline]
+    pub fn bandwidth(self) -> Hz {
+        Hz(erb_bandwidth_from_hz(self.hz()))
+    }
+}
+
+impl Add for Erb {
+    type Output = Erb;
+    #[inline]
+    fn add(self, rhs: Erb) -> Erb {
+        Erb(self.erb() + rhs.erb())
+    }
+}
+
+impl Sub for Erb {
+    type Output = Erb;
+    #[inline]
+    fn sub(self, rhs: Erb) -> Erb {
+        Erb(self.erb() - rhs.erb())
+    }
+}
+
+impl Neg for Erb {
+    type Output = Erb;
+    #[inline]
+    fn neg(self) -> Erb {
+        Erb(-self.erb())
+    }
+}
+
+impl PartialEq for Erb {
+    #[inline]
+    fn eq(&self, other: &Erb) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Erb {}
+
+impl PartialOrd for Erb {
+    #[inline]
+    fn partial_cmp(&self, other: &Erb) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Erb {
+    #[inline]
+    fn cmp(&self, other: &Erb) -> Ordering {
+        self.erb().total_cmp(&other.erb())
+    }
+}
+
+impl Hash for Erb {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.erb().to_bits().hash(state);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{calc, LetterOctave};
+use std::error::Error;
+use std::fmt;
+
+/// The reasons for which a checked pitch conversion may fail.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PitchError {
+    /// A value given to a conversion was NaN or infinite.
+    NonFinite,
+    /// A frequency was zero or negative where a positive frequency is required.
+    NonPositiveFrequency(calc::Hz),
+    /// A frequency was negative where a non-negative frequency is required.
+    NegativeFrequency(calc::Hz),
+    /// The octave was too large in magnitude for the pitch to be represented as a step.
+    OctaveOutOfRange,
+    /// A finite value was too large in magnitude for the result of the conversion to be finite, or
+    /// a step for its frequency to be positive.
+    OutOfRange(f64),
+    /// A scale weight was zero, negative or not finite.
+    InvalidWeight(calc::Weight),
+    /// A scaled percentage was negative, which lies outside the domain of its curve.
+    NegativeScaledPerc(calc::Perc),
+    /// A frequency range was inverted, empty, non-positive or not finite.
+    InvalidRange(calc::Hz, calc::Hz),
+    /// A range of steps was inverted, empty or not finite.
+    InvalidStepRange(calc::Step, calc::Step),
+    /// A range of notes was inverted.
+    InvalidNoteRange(LetterOctave, LetterOctave),
+    /// A note lay outside of the range within which it was required.
+    NoteOutOfRange(LetterOctave),
+    /// The weights of a distribution were negative, not finite or all zero.
+    InvalidDistribution,
+    /// The number of samples in an FFT frame was zero.
+    ZeroFftSize,
+    /// A filterbank was given no bands.
+    ZeroBands,
+}
+
+impl fmt::Display for PitchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PitchError::NonFinite => write!(f, "pitch value is not finite"),
+            PitchError::NonPositiveFrequency(hz) => {
+                write!(f, "frequency must be positive but was {}hz", hz)
+            }
+            PitchError::NegativeFrequency(hz) => {
+                write!(f, "frequency must not be negative but was {}hz", hz)
+            }
+            PitchError::OctaveOutOfRange => write!(f, "octave is out of range"),
+            PitchError::OutOfRange(value) => write!(f, "value {} is out of range", value),
+            PitchError::InvalidWeight(weight) => {
+                write!(
+                    f,
+                    "scale weight must be positive and finite but was {}",
+                    weight
+                )
+            }
+            PitchError::NegativeScaledPerc(perc) => {
+                write!(f, "scaled percentage must not be negative but was {}", perc)
+            }
+            PitchError::InvalidRange(min, max) => {
+                write!(f, "invalid frequency range {}hz..{}hz", min, max)
+            }
+            PitchError::InvalidStepRange(low, high) => {
+                write!(f, "invalid step range {}..{}", low, high)
+            }
+            PitchError::InvalidNoteRange(low, high) => {
+                write!(f, "invalid note range {}..{}", low, high)
+            }
+            PitchError::NoteOutOfRange(note) => write!(f, "note {} is out of range", note),
+            PitchError::InvalidDistribution => write!(
+                f,
+                "distribution weights must be finite, non-negative and not all zero"
+            ),
+            PitchError::ZeroFftSize => write!(f, "FFT size must not be zero"),
+            PitchError::ZeroBands => write!(f, "filterbank must have at least one band"),
+        }
+    }
+}
+
+impl Error for PitchError {}
+
+/// The reasons for which a pitch may fail to parse from a string.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParsePitchError {
+    /// The string was empty or only whitespace.
+    Empty,
+    /// The string was not a recognised pitch.
+    Invalid(String),
+}
+
+impl ParsePitchError {
+    pub(crate) fn invalid(s: &str) -> ParsePitchError {
+        ParsePitchError::Invalid(s.to_string())
+    }
+}
+
+impl fmt::Display for ParsePitchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePitchError::Empty => write!(f, "cannot parse a pitch from an empty string"),
+            ParsePitchError::Invalid(s) => write!(f, "invalid pitch {:?}", s),
+        }
+    }
+}
+
+impl Error for ParsePitchError {}
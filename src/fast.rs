@@ -0,0 +1,131 @@
+//! Fast approximate conversions between Step, Hz and Mel.
+//!
+//! The functions in this module mirror those of the same name in the `calc` module, replacing the
+//! calls to `powf`, `log2` and `log10` with cheap polynomial approximations of `exp2` and `log2`.
+//! They are intended for per-sample code (e.g. pitch modulation) where the precise functions
+//! dominate the cost.
+//!
+//! Over the range 20hz to 20khz every conversion is accurate to within `MAX_ERROR_CENTS` of the
+//! precise result. Inputs that are not finite, or frequencies that are not positive, produce
+//! unspecified results.
+
+use crate::calc::{Hz, Mel, Step, PITCH_INDEX, TUNING_PITCH_A4};
+use std::f32::consts::{LN_2, LOG10_2, LOG2_10, LOG2_E, SQRT_2};
+
+/// The maximum error of any conversion in this module between 20hz and 20khz, in cents.
+pub const MAX_ERROR_CENTS: f32 = 0.05;
+
+/// Approximate `2^x`.
+///
+/// The relative error is less than `5e-6` for `x` within `-126.0..127.0`. Values outside of this
+/// range are clamped to it.
+#[inline]
+pub fn exp2(x: f32) -> f32 {
+    let x = x.clamp(-126.0, 127.0);
+    // Split into a whole part (applied directly to the exponent bits) and a fraction in -0.5..0.5.
+    let whole = (x + if x < 0.0 { -0.5 } else { 0.5 }) as i32;
+    let y = (x - whole as f32) * LN_2;
+    // 5th order Taylor series of `e^y`.
+    let p = 1.0 + y * (1.0 + y * (1.0 / 2.0 + y * (1.0 / 6.0 + y * (1.0 / 24.0 + y / 120.0))));
+    p * f32::from_bits(((whole + 127) as u32) << 23)
+}
+
+/// Approximate `log2(x)` for positive, normal `x`.
+///
+/// The absolute error is less than `5e-6`.
+#[inline]
+pub fn log2(x: f32) -> f32 {
+    let bits = x.to_bits();
+    let mut exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    // The mantissa in the range 1..2, folded into sqrt(0.5)..sqrt(2) to keep the series short.
+    let mut m = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000);
+    if m > SQRT_2 {
+        m *= 0.5;
+        exponent += 1;
+    }
+    // ln(m) = 2 * atanh(s) = 2 * (s + s^3/3 + s^5/5 + ..) where s = (m - 1) / (m + 1).
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    exponent as f32 + s * (2.0 * LOG2_E) * (1.0 + s2 * (1.0 / 3.0 + s2 * (1.0 / 5.0)))
+}
+
+/// Approximate hz from pitch as `step`.
+#[inline]
+pub fn hz_from_step(step: Step) -> Hz {
+    PITCH_INDEX * exp2((step - TUNING_PITCH_A4) * (1.0 / 12.0))
+}
+
+/// Approximate the pitch `step` from frequency in hz.
+#[inline]
+pub fn step_from_hz(hz: Hz) -> Step {
+    log2(hz * (1.0 / PITCH_INDEX)) * 12.0 + TUNING_PITCH_A4
+}
+
+/// Approximate hz from mel.
+#[inline]
+pub fn hz_from_mel(mel: Mel) -> Hz {
+    (exp2(mel * (LOG2_10 / 2595.0)) - 1.0) * 700.0
+}
+
+/// Approximate mel from hz.
+#[inline]
+pub fn mel_from_hz(hz: Hz) -> Mel {
+    log2(1.0 + hz * (1.0 / 700.0)) * (2595.0 * LOG10_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Logarithmically spaced frequencies across the range 20hz to 20khz.
+    fn audible() -> impl Iterator<Item = f64> {
+        const N: i32 = 10_000;
+        (0..=N).map(|i| 20.0 * 1000f64.powf(f64::from(i) / f64::from(N)))
+    }
+
+    fn cents(hz: f64, reference: f64) -> f64 {
+        (1200.0 * (hz / reference).log2()).abs()
+    }
+
+    fn assert_within_max_error(cents: f64, hz: f64) {
+        assert!(
+            cents < f64::from(MAX_ERROR_CENTS),
+            "{}¢ error at {}hz",
+            cents,
+            hz
+        );
+    }
+
+    #[test]
+    fn step_hz_error() {
+        for hz in audible() {
+            let step = 69.0 + 12.0 * (hz / 440.0).log2();
+            let fast_step = f64::from(step_from_hz(hz as f32));
+            assert_within_max_error((fast_step - step).abs() * 100.0, hz);
+            let fast_hz = f64::from(hz_from_step(step as f32));
+            assert_within_max_error(cents(fast_hz, hz), hz);
+        }
+    }
+
+    #[test]
+    fn mel_hz_error() {
+        for hz in audible() {
+            let mel = 2595.0 * (1.0 + hz / 700.0).log10();
+            let fast_mel = f64::from(mel_from_hz(hz as f32));
+            let hz_from_fast_mel = (10f64.powf(fast_mel / 2595.0) - 1.0) * 700.0;
+            assert_within_max_error(cents(hz_from_fast_mel, hz), hz);
+            let fast_hz = f64::from(hz_from_mel(mel as f32));
+            assert_within_max_error(cents(fast_hz, hz), hz);
+        }
+    }
+
+    #[test]
+    fn exp2_log2() {
+        for i in -1000..1000 {
+            let x = i as f32 * 0.1;
+            assert!((exp2(x) / x.exp2() - 1.0).abs() < 5e-6, "exp2({})", x);
+            let y = x.exp2();
+            assert!((log2(y) - x).abs() < 5e-6, "log2({})", y);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Triangular filterbanks spaced evenly along the Mel, Bark or ERB-rate scales.
+//!
+//! The weights are generated in the same manner as librosa's `filters.mel`, so that a `Filterbank`
+//! applied to a magnitude or power spectrum produces the usual input for MFCC-style features.
+
+use crate::{
+    bark_from_hz_with_scale, calc, erb_from_hz, hz_from_bark_with_scale, hz_from_erb,
+    hz_from_mel_with_scale, mel_from_hz_with_scale, BarkScale, BinMapper, FrequencyRange, MelScale,
+    PitchError,
+};
+
+/// The scale along which the centre frequencies of the filters are evenly spaced.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FilterScale {
+    Mel(MelScale),
+    Bark(BarkScale),
+    Erb,
+}
+
+/// How the weights of each filter are scaled.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Normalization {
+    /// Each filter peaks at `1.0`, as in HTK.
+    Peak,
+    /// Each filter is divided by its bandwidth so that it has unit area, as in Slaney's Auditory
+    /// Toolbox and librosa's `norm="slaney"`.
+    Area,
+}
+
+/// A single triangular filter, stored sparsely as the weights of a contiguous range of FFT bins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    /// The index of the FFT bin to which the first weight applies.
+    pub start: usize,
+    /// The weights of the bins `start..start + weights.len()`.
+    pub weights: Vec<f32>,
+}
+
+/// A bank of triangular filters over the bins of a real FFT.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filterbank {
+    /// The filters in order of ascending centre frequency.
+    pub filters: Vec<Filter>,
+    /// The number of FFT bins, i.e. `fft_size / 2 + 1`.
+    pub bins: usize,
+}
+
+impl FilterScale {
+    /// Convert the frequency in hz to a value on this scale.
+    #[inline]
+    pub fn from_hz(self, hz: calc::Hz) -> f32 {
+        match self {
+            FilterScale::Mel(scale) => mel_from_hz_with_scale(hz, scale),
+            FilterScale::Bark(scale) => bark_from_hz_with_scale(hz, scale),
+            FilterScale::Erb => erb_from_hz(hz),
+        }
+    }
+
+    /// Convert the value on this scale to a frequency in hz.
+    #[inline]
+    pub fn to_hz(self, value: f32) -> calc::Hz {
+        match self {
+            FilterScale::Mel(scale) => hz_from_mel_with_scale(value, scale),
+            FilterScale::Bark(scale) => hz_from_bark_with_scale(value, scale),
+            FilterScale::Erb => hz_from_erb(value),
+        }
+    }
+}
+
+impl Filter {
+    /// The weight applied to the given FFT bin.
+    #[inline]
+    pub fn weight(&self, bin: usize) -> f32 {
+        bin.checked_sub(self.start)
+            .and_then(|i| self.weights.get(i))
+            .cloned()
+            .unwrap_or(0.0)
+    }
+}
+
+impl Filterbank {
+    /// Generate `bands` triangular filters for an FFT of `fft_size` samples at `sample_rate`.
+    ///
+    /// The edges of the filters are spaced evenly along `scale` across `range`, so that each
+    /// filter starts at the centre of the previous filter and ends at the centre of the next.
+    ///
+    /// Returns an error if the sample rate is not finite and positive, or if `fft_size` or `bands`
+    /// is zero.
+    pub fn new(
+        sample_rate: calc::Hz,
+        fft_size: usize,
+        bands: usize,
+        range: FrequencyRange,
+        scale: FilterScale,
+        normalization: Normalization,
+    ) -> Result<Filterbank, PitchError> {
+        let mapper = BinMapper::new(sample_rate, fft_size)?;
+        if bands == 0 {
+            return Err(PitchError::ZeroBands);
+        }
+        let bins = mapper.bins();
+        let (scale_min, scale_max) = (scale.from_hz(range.min()), scale.from_hz(range.max()));
+        let edges: Vec<calc::Hz> = (0..bands + 2)
+            .map(|i| scale_min + (scale_max - scale_min) * i as f32 / (bands + 1) as f32)
+            .map(|value| scale.to_hz(value))
+            .collect();
+
+        let filters = edges
+            .windows(3)
+            .map(|edges| {
+                let (low, centre, high) = (edges[0], edges[1], edges[2]);
+                let gain = match normalization {
+                    Normalization::Peak => 1.0,
+                    Normalization::Area => 2.0 / (high - low),
+                };
+                let weight = |bin: usize| {
+                    let hz = mapper.bin_to_hz(bin as f32).hz();
+                    let rising = (hz - low) / (centre - low);
+                    let falling = (high - hz) / (high - centre);
+                    rising.min(falling).max(0.0) * gain
+                };
+                let start = (0..bins).find(|&bin| weight(bin) > 0.0).unwrap_or(bins);
+                let end = (start..bins)
+                    .find(|&bin| weight(bin) <= 0.0)
+                    .unwrap_or(bins);
+                Filter {
+                    start,
+                    weights: (start..end).map(weight).collect(),
+                }
+            })
+            .collect();
+
+        Ok(Filterbank { filters, bins })
+    }
+
+    /// The weights as a dense `filters.len()` by `bins` matrix.
+    pub fn to_dense(&self) -> Vec<Vec<f32>> {
+        self.filters
+            .iter()
+            .map(|filter| (0..self.bins).map(|bin| filter.weight(bin)).collect())
+            .collect()
+    }
+
+    /// Apply the filterbank to the spectrum, writing the energy of each filter into `output`.
+    ///
+    /// Panics if `spectrum` does not have `bins` elements or `output` does not have one element
+    /// per filter.
+    pub fn apply(&self, spectrum: &[f32], output: &mut [f32]) {
+        assert_eq!(spectrum.len(), self.bins);
+        assert_eq!(output.len(), self.filters.len());
+        for (filter, out) in self.filters.iter().zip(output) {
+            let bins = &spectrum[filter.start..filter.start + filter.weights.len()];
+            *out = bins.iter().zip(&filter.weights).map(|(s, w)| s * w).sum();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 22_050.0;
+    const FFT_SIZE: usize = 2048;
+
+    fn filterbank(scale: FilterScale, normalization: Normalization) -> Filterbank {
+        let range = FrequencyRange::new(20.0, SAMPLE_RATE / 2.0).unwrap();
+        Filterbank::new(SAMPLE_RATE, FFT_SIZE, 40, range, scale, normalization).unwrap()
+    }
+
+    #[test]
+    fn peak_normalization() {
+        for &scale in &[
+            FilterScale::Mel(MelScale::Slaney),
+            FilterScale::Mel(MelScale::Htk),
+            FilterScale::Bark(BarkScale::Traunmuller),
+            FilterScale::Erb,
+        ] {
+            let bank = filterbank(scale, Normalization::Peak);
+            assert_eq!(bank.filters.len(), 40);
+            assert_eq!(bank.to_dense()[0].len(), FFT_SIZE / 2 + 1);
+            for filter in &bank.filters {
+                let peak = filter.weights.iter().cloned().fold(0.0, f32::max);
+                assert!(peak > 0.0 && peak <= 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn area_normalization() {
+        let bank = filterbank(FilterScale::Mel(MelScale::Slaney), Normalization::Area);
+        let bin_hz = SAMPLE_RATE / FFT_SIZE as f32;
+        // Filters narrower than a few bins are too coarsely sampled to integrate accurately.
+        for filter in bank.filters.iter().filter(|f| f.weights.len() > 8) {
+            let area: f32 = filter.weights.iter().sum::<f32>() * bin_hz;
+            assert!((area - 1.0).abs() < 0.05, "{}", area);
+        }
+    }
+
+    #[test]
+    fn apply() {
+        let bank = filterbank(FilterScale::Erb, Normalization::Peak);
+        let spectrum = vec![1.0; bank.bins];
+        let mut output = vec![0.0; bank.filters.len()];
+        bank.apply(&spectrum, &mut output);
+        for (filter, &energy) in bank.filters.iter().zip(&output) {
+            assert_eq!(energy, filter.weights.iter().sum::<f32>());
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let range = FrequencyRange::default();
+        let scale = FilterScale::Erb;
+        let new = |sample_rate, fft_size, bands| {
+            Filterbank::new(
+                sample_rate,
+                fft_size,
+                bands,
+                range,
+                scale,
+                Normalization::Peak,
+            )
+        };
+        assert_eq!(new(SAMPLE_RATE, 0, 40), Err(PitchError::ZeroFftSize));
+        assert_eq!(new(SAMPLE_RATE, FFT_SIZE, 0), Err(PitchError::ZeroBands));
+        assert_eq!(
+            new(-1.0, FFT_SIZE, 40),
+            Err(PitchError::NonPositiveFrequency(-1.0))
+        );
+        assert_eq!(new(SAMPLE_RATE, FFT_SIZE, 1).unwrap().filters.len(), 1);
+    }
+}
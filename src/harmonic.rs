@@ -0,0 +1,102 @@
+//! Harmonic series and inharmonic partials.
+//!
+//! The partials of an ideal string lie at whole multiples of the fundamental. The partials of a
+//! stiff string such as a piano string are stretched progressively sharp according to its
+//! inharmonicity coefficient `B`, so that partial `n` lies at `n * f0 * sqrt(1 + B * n^2)`.
+
+use crate::{calc, Hz, LetterOctave};
+
+/// A single partial of a harmonic series, along with the nearest equal tempered note.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Harmonic {
+    /// The number of the partial, where `1` is the fundamental.
+    pub number: u32,
+    /// The frequency of the partial.
+    pub hz: Hz,
+    /// The equal tempered note nearest to the partial.
+    pub letter_octave: LetterOctave,
+    /// The deviation of the partial from `letter_octave` in cents, within -50..50.
+    pub cents: f32,
+}
+
+impl Harmonic {
+    /// Describe the `number`th partial at the frequency `hz`.
+    pub fn new(number: u32, hz: Hz) -> Harmonic {
+        let step = hz.step();
+        Harmonic {
+            number,
+            hz,
+            letter_octave: LetterOctave::from(hz),
+            cents: (step - step.round()) * 100.0,
+        }
+    }
+}
+
+/// The frequency of partial `number` of a stiff string with the given fundamental and
+/// inharmonicity coefficient.
+///
+/// An inharmonicity of `0.0` gives the ideal harmonic `number * fundamental`.
+#[inline]
+pub fn partial_hz(fundamental: calc::Hz, number: u32, inharmonicity: f32) -> calc::Hz {
+    let n = number as f32;
+    n * fundamental * (1.0 + inharmonicity * n * n).sqrt()
+}
+
+/// The inharmonicity coefficient of a stiff string, given its fundamental and the measured
+/// frequency of partial `number`.
+#[inline]
+pub fn inharmonicity_from_partial(fundamental: calc::Hz, number: u32, partial: calc::Hz) -> f32 {
+    let n = number as f32;
+    let stretch = partial / (n * fundamental);
+    (stretch * stretch - 1.0) / (n * n)
+}
+
+/// The first `count` harmonics of the fundamental, starting with the fundamental itself.
+pub fn harmonic_series(fundamental: Hz, count: u32) -> Vec<Harmonic> {
+    inharmonic_series(fundamental, count, 0.0)
+}
+
+/// The first `count` partials of a stiff string with the given fundamental and inharmonicity
+/// coefficient, starting with the fundamental itself.
+pub fn inharmonic_series(fundamental: Hz, count: u32, inharmonicity: f32) -> Vec<Harmonic> {
+    (1..=count)
+        .map(|n| Harmonic::new(n, Hz(partial_hz(fundamental.hz(), n, inharmonicity))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Letter;
+
+    #[test]
+    fn harmonics() {
+        let a2 = Hz(110.0);
+        assert_eq!(
+            a2.harmonics(4),
+            vec![Hz(110.0), Hz(220.0), Hz(330.0), Hz(440.0)]
+        );
+        assert_eq!(a2.subharmonics(2), vec![Hz(110.0), Hz(55.0)]);
+        assert!(a2.harmonics(0).is_empty());
+
+        let series = harmonic_series(a2, 7);
+        assert_eq!(series[2].letter_octave, LetterOctave(Letter::E, 4));
+        assert!((series[2].cents - 1.955).abs() < 0.01);
+        assert_eq!(series[4].letter_octave, LetterOctave(Letter::Csh, 5));
+        assert!((series[4].cents + 13.686).abs() < 0.01);
+        assert_eq!(series[6].letter_octave, LetterOctave(Letter::G, 5));
+        assert!((series[6].cents + 31.174).abs() < 0.01);
+    }
+
+    #[test]
+    fn inharmonicity() {
+        let b = 0.0004;
+        let partials = Hz(100.0).partials(10, b);
+        assert_eq!(partials[0].hz(), partial_hz(100.0, 1, b));
+        assert!(partials[9] > Hz(1000.0));
+        let estimated = inharmonicity_from_partial(100.0, 10, partials[9].hz());
+        assert!((estimated - b).abs() < 1e-6);
+        let series = inharmonic_series(Hz(100.0), 10, b);
+        assert!(series.windows(2).all(|w| w[0].hz < w[1].hz));
+    }
+}
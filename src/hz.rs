@@ -1,24 +1,36 @@
+use super::harmonic::partial_hz;
 use super::{
-    calc, letter_octave_from_hz, mel_from_hz, perc_from_hz, scaled_perc_from_hz, step_from_hz,
-    Letter, LetterOctave, Mel, Octave, Perc, ScaleWeight, ScaledPerc, Step, DEFAULT_SCALE_WEIGHT,
+    bark_from_hz, calc, erb_bandwidth_from_hz, erb_from_hz, letter_octave_from_hz, mel_from_hz,
+    mel_from_hz_with_scale, perc_from_hz, perc_from_hz_in_range, scaled_perc_from_hz,
+    scaled_perc_from_hz_in_range, scaled_perc_from_hz_with_curve, step_from_hz, Bark, Curve, Erb,
+    Letter, LetterOctave, Mel, MelFormula, MelScale, Octave, ParsePitchError, Perc, Pitch,
+    PitchError, Ratio, ScaleWeight, ScaledPerc, Step, DEFAULT_SCALE_WEIGHT,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Neg, Range, Sub};
+use std::str::FromStr;
 
 pub const MAX: calc::Hz = 20_000.0;
 pub const MIN: calc::Hz = 20.0;
 
 /// Pitch representation in the form of a frequency (hz).
+///
+/// Equality, ordering and hashing follow `f32::total_cmp` rather than IEEE comparison, so they
+/// never panic and `Hz` may be safely sorted or used as a `BTreeMap` or `HashMap` key. As a result
+/// `-0.0` is ordered before `0.0` and NaN is equal to itself, ordered after all numbers.
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde::HzRepr"))]
 pub struct Hz(pub calc::Hz);
 
 impl Hz {
     /// Return the unit value of the Hz struct.
     #[inline]
-    pub fn hz(self) -> calc::Hz {
+    pub const fn hz(self) -> calc::Hz {
         let Hz(hz) = self;
         hz
     }
@@ -45,10 +57,10 @@ impl Hz {
     }
 
     /// Convert to a LetterOctave struct with the same pitch.
+    #[deprecated(note = "use Pitch::to_letter_octave")]
     #[inline]
     pub fn to_letter_octave(self) -> LetterOctave {
-        let (letter, octave) = self.letter_octave();
-        LetterOctave(letter, octave)
+        Pitch::to_letter_octave(self)
     }
 
     /// Convert to the unit value of a Mel.
@@ -58,9 +70,54 @@ impl Hz {
     }
 
     /// Convert to a Mel struct.
+    #[deprecated(note = "use Pitch::to_mel")]
     #[inline]
     pub fn to_mel(self) -> Mel {
-        Mel(self.mel())
+        Pitch::to_mel(self)
+    }
+
+    /// Convert to the unit value of a Bark.
+    #[inline]
+    pub fn bark(self) -> calc::Bark {
+        bark_from_hz(self.hz())
+    }
+
+    /// Convert to a Bark struct.
+    #[deprecated(note = "use Pitch::to_bark")]
+    #[inline]
+    pub fn to_bark(self) -> Bark {
+        Pitch::to_bark(self)
+    }
+
+    /// Convert to the unit value of an Erb.
+    #[inline]
+    pub fn erb(self) -> calc::Erb {
+        erb_from_hz(self.hz())
+    }
+
+    /// Convert to an Erb struct.
+    #[deprecated(note = "use Pitch::to_erb")]
+    #[inline]
+    pub fn to_erb(self) -> Erb {
+        Pitch::to_erb(self)
+    }
+
+    /// The equivalent rectangular bandwidth of an auditory filter centred on this frequency.
+    #[inline]
+    pub fn erb_bandwidth(self) -> Hz {
+        Hz(erb_bandwidth_from_hz(self.hz()))
+    }
+
+    /// Convert to the unit value of a Mel on the given scale.
+    #[inline]
+    pub fn mel_with_scale(self, scale: MelScale) -> calc::Mel {
+        mel_from_hz_with_scale(self.hz(), scale)
+    }
+
+    /// Convert to a Mel struct on the given scale, e.g. `hz.to_mel_with_scale(Slaney)`.
+    #[inline]
+    pub fn to_mel_with_scale<S: MelFormula>(self, scale: S) -> Mel<S> {
+        Mel(self.mel_with_scale(S::SCALE), scale)
     }
 
     /// Convert to the unit value of a Perc struct.
@@ -71,9 +128,10 @@ impl Hz {
     }
 
     /// Convert to a percentage of the human hearing range.
+    #[deprecated(note = "use Pitch::to_perc")]
     #[inline]
     pub fn to_perc(self) -> Perc {
-        Perc(self.perc())
+        Pitch::to_perc(self)
     }
 
     /// Convert to a scaled percentage of the human hearing range with a given weight.
@@ -96,9 +154,46 @@ impl Hz {
     }
 
     /// Convert to a scaled percentage of the human hearing range.
+    #[deprecated(note = "use Pitch::to_scaled_perc")]
     #[inline]
     pub fn to_scaled_perc(self) -> ScaledPerc {
-        self.to_scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+        Pitch::to_scaled_perc(self)
+    }
+
+    /// Convert to a percentage scaled along the given curve.
+    #[inline]
+    pub fn scaled_perc_with_curve<C: Curve>(self, curve: &C) -> calc::Perc {
+        scaled_perc_from_hz_with_curve(self.hz(), curve)
+    }
+
+    /// Convert to a ScaledPerc struct scaled along the given curve.
+    #[inline]
+    pub fn to_scaled_perc_with_curve<C: Curve>(self, curve: C) -> ScaledPerc<C> {
+        ScaledPerc(self.scaled_perc_with_curve(&curve), curve)
+    }
+
+    /// Convert to a percentage of the given frequency range.
+    #[inline]
+    pub fn perc_in_range(self, range: FrequencyRange) -> calc::Perc {
+        perc_from_hz_in_range(self.hz(), range)
+    }
+
+    /// Convert to a Perc struct measured over the given frequency range.
+    #[inline]
+    pub fn to_perc_in_range(self, range: FrequencyRange) -> Perc {
+        Perc(self.perc_in_range(range))
+    }
+
+    /// Convert to a scaled percentage of the given frequency range with a given weight.
+    #[inline]
+    pub fn scaled_perc_in_range(self, weight: ScaleWeight, range: FrequencyRange) -> calc::Perc {
+        scaled_perc_from_hz_in_range(self.hz(), weight, range)
+    }
+
+    /// Convert to a ScaledPerc struct measured over the given frequency range.
+    #[inline]
+    pub fn to_scaled_perc_in_range(self, weight: ScaleWeight, range: FrequencyRange) -> ScaledPerc {
+        ScaledPerc(self.scaled_perc_in_range(weight, range), weight)
     }
 
     /// Convert to the unit value of a Step.
@@ -109,9 +204,177 @@ impl Hz {
     }
 
     /// Convert to a floating point MIDI-esque Step.
+    #[deprecated(note = "use Pitch::to_step")]
     #[inline]
     pub fn to_step(self) -> Step {
-        Step(self.step())
+        Pitch::to_step(self)
+    }
+
+    /// The first `count` harmonics, starting with this frequency as the fundamental.
+    pub fn harmonics(self, count: u32) -> Vec<Hz> {
+        self.partials(count, 0.0)
+    }
+
+    /// The first `count` subharmonics (`hz / n`), starting with this frequency itself.
+    pub fn subharmonics(self, count: u32) -> Vec<Hz> {
+        (1..=count).map(|n| Hz(self.hz() / n as calc::Hz)).collect()
+    }
+
+    /// The first `count` partials of a stiff string with this fundamental and the given
+    /// inharmonicity coefficient.
+    pub fn partials(self, count: u32, inharmonicity: f32) -> Vec<Hz> {
+        (1..=count)
+            .map(|n| Hz(partial_hz(self.hz(), n, inharmonicity)))
+            .collect()
+    }
+}
+
+/// A range of frequencies over which `Perc` and `ScaledPerc` are measured.
+///
+/// A percentage of `0.0` corresponds to `min` and `1.0` to `max`. The conversions that do not take
+/// a range use `DEFAULT_FREQUENCY_RANGE`, the human hearing range `MIN..MAX`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::serde::FrequencyRangeRepr")
+)]
+pub struct FrequencyRange {
+    min: calc::Hz,
+    max: calc::Hz,
+}
+
+/// The human hearing range, used by all percentage conversions that do not take a range.
+pub const DEFAULT_FREQUENCY_RANGE: FrequencyRange = FrequencyRange { min: MIN, max: MAX };
+
+impl FrequencyRange {
+    /// A constructor for a FrequencyRange.
+    ///
+    /// Returns an error unless `min` and `max` are finite and `0 < min < max`.
+    pub fn new(min: calc::Hz, max: calc::Hz) -> Result<FrequencyRange, PitchError> {
+        if min.is_finite() && max.is_finite() && 0.0 < min && min < max {
+            Ok(FrequencyRange { min, max })
+        } else {
+            Err(PitchError::InvalidRange(min, max))
+        }
+    }
+
+    /// The lowest frequency in the range.
+    #[inline]
+    pub const fn min(self) -> calc::Hz {
+        self.min
+    }
+
+    /// The highest frequency in the range.
+    #[inline]
+    pub const fn max(self) -> calc::Hz {
+        self.max
+    }
+
+    /// The width of the range in hz.
+    #[inline]
+    pub fn span(self) -> calc::Hz {
+        self.max - self.min
+    }
+
+    /// Whether the frequency lies within the range, inclusive of both ends.
+    #[inline]
+    pub fn contains(self, hz: Hz) -> bool {
+        self.min <= hz.hz() && hz.hz() <= self.max
+    }
+
+    /// The nearest frequency within the range.
+    #[inline]
+    pub fn clamp(self, hz: Hz) -> Hz {
+        Hz(hz.hz().max(self.min).min(self.max))
+    }
+}
+
+impl Default for FrequencyRange {
+    fn default() -> FrequencyRange {
+        DEFAULT_FREQUENCY_RANGE
+    }
+}
+
+/// Maps between the bins of a real FFT and frequency.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde::BinMapperRepr"))]
+pub struct BinMapper {
+    /// The sample rate of the signal in hz.
+    pub sample_rate: calc::Hz,
+    /// The number of samples in each FFT frame.
+    pub fft_size: usize,
+}
+
+impl BinMapper {
+    /// A constructor for a BinMapper.
+    ///
+    /// Returns an error if the sample rate is not finite and positive or `fft_size` is zero.
+    pub fn new(sample_rate: calc::Hz, fft_size: usize) -> Result<BinMapper, PitchError> {
+        calc::check_hz(sample_rate)?;
+        if fft_size == 0 {
+            return Err(PitchError::ZeroFftSize);
+        }
+        Ok(BinMapper {
+            sample_rate,
+            fft_size,
+        })
+    }
+
+    /// The number of bins from 0hz up to and including the nyquist frequency.
+    #[inline]
+    pub fn bins(self) -> usize {
+        self.fft_size / 2 + 1
+    }
+
+    /// The distance between the centre frequencies of neighbouring bins in hz.
+    #[inline]
+    pub fn bin_width(self) -> calc::Hz {
+        self.sample_rate / self.fft_size as calc::Hz
+    }
+
+    /// The frequency at the given (possibly fractional) bin.
+    #[inline]
+    pub fn bin_to_hz(self, bin: f32) -> Hz {
+        Hz(bin * self.bin_width())
+    }
+
+    /// The (possibly fractional) bin at the given frequency.
+    #[inline]
+    pub fn hz_to_bin(self, hz: Hz) -> f32 {
+        hz.hz() / self.bin_width()
+    }
+
+    /// The range of bins whose centre frequencies lie within a quarter tone of the given note.
+    ///
+    /// The range is empty if the bins are too wide to resolve the note.
+    pub fn bins_for_note(self, note: LetterOctave) -> Range<usize> {
+        let step = note.step();
+        let low = self.hz_to_bin(Hz::from(Step(step - 0.5))).ceil().max(0.0) as usize;
+        let high = self.hz_to_bin(Hz::from(Step(step + 0.5))).floor() as usize + 1;
+        low.min(self.bins())..high.min(self.bins())
+    }
+
+    /// Estimate the frequency of a spectral peak at `bin` by fitting a parabola through the
+    /// magnitudes of the bin and its two neighbours.
+    ///
+    /// Interpolating log magnitudes (e.g. decibels) gives the most accurate estimates. Falls back
+    /// to the frequency of `bin` itself if the bin has fewer than two neighbours.
+    pub fn interpolate_peak(self, magnitudes: &[f32], bin: usize) -> Hz {
+        let offset = match (bin.checked_sub(1), magnitudes.get(bin + 1)) {
+            (Some(prev), Some(&next)) => {
+                let (prev, peak) = (magnitudes[prev], magnitudes[bin]);
+                let curvature = prev - 2.0 * peak + next;
+                if curvature == 0.0 {
+                    0.0
+                } else {
+                    0.5 * (prev - next) / curvature
+                }
+            }
+            _ => 0.0,
+        };
+        self.bin_to_hz(bin as f32 + offset)
     }
 }
 
@@ -131,42 +394,34 @@ impl Sub for Hz {
     }
 }
 
-impl Mul for Hz {
-    type Output = Hz;
-    #[inline]
-    fn mul(self, rhs: Hz) -> Hz {
-        Hz(self.hz() * rhs.hz())
-    }
-}
-
-impl Div for Hz {
+impl Neg for Hz {
     type Output = Hz;
     #[inline]
-    fn div(self, rhs: Hz) -> Hz {
-        Hz(self.hz() / rhs.hz())
+    fn neg(self) -> Hz {
+        Hz(-self.hz())
     }
 }
 
-impl Rem for Hz {
+impl Mul<Ratio> for Hz {
     type Output = Hz;
     #[inline]
-    fn rem(self, rhs: Hz) -> Hz {
-        Hz(self.hz() % rhs.hz())
+    fn mul(self, rhs: Ratio) -> Hz {
+        Hz(self.hz() * rhs.ratio())
     }
 }
 
-impl Neg for Hz {
+impl Div<Ratio> for Hz {
     type Output = Hz;
     #[inline]
-    fn neg(self) -> Hz {
-        Hz(-self.hz())
+    fn div(self, rhs: Ratio) -> Hz {
+        Hz(self.hz() / rhs.ratio())
     }
 }
 
 impl PartialEq for Hz {
     #[inline]
     fn eq(&self, other: &Hz) -> bool {
-        self.hz() == other.hz()
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -175,6 +430,208 @@ impl Eq for Hz {}
 impl PartialOrd for Hz {
     #[inline]
     fn partial_cmp(&self, other: &Hz) -> Option<Ordering> {
-        self.hz().partial_cmp(&other.hz())
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hz {
+    #[inline]
+    fn cmp(&self, other: &Hz) -> Ordering {
+        self.hz().total_cmp(&other.hz())
+    }
+}
+
+impl Hash for Hz {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hz().to_bits().hash(state);
+    }
+}
+
+/// Formats the frequency in hz, or in khz from 1000hz upwards, e.g. `"440 Hz"` or `"1.25 kHz"`.
+///
+/// The value is written with the formatter's precision if given (`{:.3}` gives `"1.250 kHz"`),
+/// otherwise rounded to at most two decimal places with trailing zeros removed. The unit is chosen
+/// after rounding, so `Hz(999.999)` is written as `"1 kHz"`. The width, fill and alignment apply
+/// to the whole string, e.g. `{:>10}` gives `"    440 Hz"`.
+impl fmt::Display for Hz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+        let round = |value: calc::Hz| format!("{:.*}", precision.unwrap_or(2), value);
+        let mut value = round(self.hz());
+        let mut unit = "Hz";
+        if value
+            .parse::<calc::Hz>()
+            .is_ok_and(|hz| hz.abs() >= 1_000.0)
+        {
+            value = round(self.hz() / 1_000.0);
+            unit = "kHz";
+        }
+        if precision.is_none() && value.contains('.') {
+            value.truncate(value.trim_end_matches('0').trim_end_matches('.').len());
+        }
+        pad(f, &format!("{} {}", value, unit))
+    }
+}
+
+/// Write `s` padded to the formatter's width with its fill and alignment, left aligned by default.
+///
+/// Unlike `Formatter::pad` this does not truncate `s` to the precision, which has already been
+/// applied to the value.
+fn pad(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    let padding = f.width().unwrap_or(0).saturating_sub(s.chars().count());
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(s)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Parses a frequency from any of the pitch notations used by the crate:
+///
+/// - a number of hz, optionally followed by `hz`, e.g. `"440"` or `"440 Hz"`.
+/// - a number of khz, followed by `k` or `khz`, e.g. `"1.2k"` or `"1.2 kHz"`.
+/// - a MIDI step followed by `m`, e.g. `"69m"`.
+/// - a note name, optionally followed by a deviation in cents, e.g. `"A4"`, `"C#-1"` or
+///   `"Bb3 -12¢"`.
+///
+/// Units are case insensitive and may be separated from the number by whitespace.
+impl FromStr for Hz {
+    type Err = ParsePitchError;
+    fn from_str(s: &str) -> Result<Hz, ParsePitchError> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Err(ParsePitchError::Empty);
+        }
+        let invalid = || ParsePitchError::invalid(s);
+        let number = |number: &str| {
+            number
+                .trim_end()
+                .parse::<f32>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .ok_or_else(invalid)
+        };
+        // A finite number may still overflow once converted to hz.
+        let finite = |hz: Hz| {
+            Some(hz)
+                .filter(|hz| hz.hz().is_finite())
+                .ok_or_else(invalid)
+        };
+
+        if let Some((note, rest)) = LetterOctave::parse_prefix(trimmed) {
+            let cents = rest.trim_start();
+            let cents = cents
+                .strip_suffix('¢')
+                .or_else(|| cents.strip_suffix("cents"))
+                .or_else(|| cents.strip_suffix('c'))
+                .unwrap_or(cents);
+            let cents = if cents.is_empty() {
+                0.0
+            } else {
+                number(cents)?
+            };
+            return finite(Hz::from(Step(note.step() + cents / 100.0)));
+        }
+
+        let lower = trimmed.to_ascii_lowercase();
+        if let Some(step) = lower.strip_suffix('m') {
+            finite(Hz::from(Step(number(step)?)))
+        } else if let Some(khz) = lower
+            .strip_suffix("khz")
+            .or_else(|| lower.strip_suffix('k'))
+        {
+            finite(Hz(number(khz)? * 1_000.0))
+        } else {
+            finite(Hz(number(lower.strip_suffix("hz").unwrap_or(&lower))?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bin_mapper() {
+        let mapper = BinMapper::new(44_100.0, 4096).unwrap();
+        assert_eq!(mapper.bins(), 2049);
+        assert_eq!(mapper.bin_to_hz(mapper.hz_to_bin(Hz(440.0))), Hz(440.0));
+        for bin in mapper.bins_for_note(LetterOctave(Letter::A, 6)) {
+            let step = mapper.bin_to_hz(bin as f32).step();
+            assert!((step - 93.0).abs() <= 0.5);
+        }
+        assert_eq!(mapper.bins_for_note(LetterOctave(Letter::A, 6)), 159..169);
+        assert!(mapper.bins_for_note(LetterOctave(Letter::A, 0)).is_empty());
+        assert_eq!(BinMapper::new(44_100.0, 0), Err(PitchError::ZeroFftSize));
+        assert_eq!(
+            BinMapper::new(0.0, 4096),
+            Err(PitchError::NonPositiveFrequency(0.0))
+        );
+        assert_eq!(BinMapper::new(f32::NAN, 4096), Err(PitchError::NonFinite));
+    }
+
+    #[test]
+    fn interpolate_peak() {
+        let mapper = BinMapper::new(1024.0, 1024).unwrap();
+        // A parabola peaking at bin 10.25.
+        let magnitudes: Vec<f32> = (0..20).map(|i| -(i as f32 - 10.25).powi(2)).collect();
+        assert_eq!(mapper.interpolate_peak(&magnitudes, 10), Hz(10.25));
+        assert_eq!(mapper.interpolate_peak(&magnitudes, 0), Hz(0.0));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Hz(440.0).to_string(), "440 Hz");
+        assert_eq!(Hz(27.5).to_string(), "27.5 Hz");
+        assert_eq!(Hz(1_250.0).to_string(), "1.25 kHz");
+        assert_eq!(Hz(20_000.0).to_string(), "20 kHz");
+        assert_eq!(format!("{:.1}", Hz(440.0)), "440.0 Hz");
+        assert_eq!(format!("{:.3}", Hz(1_250.0)), "1.250 kHz");
+        assert_eq!(Hz(999.999).to_string(), "1 kHz");
+        assert_eq!(format!("{:.1}", Hz(999.99)), "1.0 kHz");
+        assert_eq!(Hz(999.9).to_string(), "999.9 Hz");
+        assert_eq!(format!("{:>10}", Hz(440.0)), "    440 Hz");
+        assert_eq!(format!("{:*^12.1}", Hz(440.0)), "**440.0 Hz**");
+    }
+
+    #[test]
+    fn parse() {
+        let parse = |s: &str| s.parse::<Hz>().unwrap().hz();
+        assert_eq!(parse("440"), 440.0);
+        assert_eq!(parse("440hz"), 440.0);
+        assert_eq!(parse(" 440 Hz "), 440.0);
+        assert_eq!(parse("1.2k"), 1_200.0);
+        assert_eq!(parse("1.2 kHz"), 1_200.0);
+        assert!((parse("A4") - 440.0).abs() < 1e-3);
+        assert!((parse("69m") - 440.0).abs() < 1e-3);
+        assert!((parse("60.5 m") - Step(60.5).hz()).abs() < 1e-3);
+        assert!((parse("C#-1") - Step(1.0).hz()).abs() < 1e-3);
+        assert!((parse("A4 +100¢") - Step(70.0).hz()).abs() < 1e-2);
+        assert_eq!(Hz(1_250.0).to_string().parse::<Hz>(), Ok(Hz(1_250.0)));
+        assert_eq!("".parse::<Hz>(), Err(ParsePitchError::Empty));
+        for invalid in [
+            "H4",
+            "440 MHz",
+            "inf",
+            "A4 sharp",
+            "k",
+            "C999999999",
+            "B#2147483647",
+            "99999999999m",
+            "1e38k",
+            "A4 +99999999999¢",
+        ] {
+            assert!(invalid.parse::<Hz>().is_err(), "{}", invalid);
+        }
     }
 }
@@ -0,0 +1,226 @@
+//! A catalogue of instrument ranges, including transposing instruments.
+//!
+//! Music for a transposing instrument is written at a different pitch to that which sounds. The
+//! `transposition` of an instrument is the interval from a written note to the concert pitch that
+//! sounds, e.g. a written C on a clarinet in Bb sounds the Bb a major second below.
+
+use crate::{Interval, Letter, LetterOctave};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::ops::RangeInclusive;
+
+/// An instrument or voice, and the range of notes that may be written for it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Instrument {
+    pub name: Cow<'static, str>,
+    /// The interval from a written note to the concert pitch that sounds.
+    pub transposition: Interval,
+    /// The lowest and highest written notes.
+    pub written_range: RangeInclusive<LetterOctave>,
+}
+
+impl Instrument {
+    /// An instrument with the given transposition and written range.
+    pub const fn new(
+        name: &'static str,
+        transposition: Interval,
+        written_range: RangeInclusive<LetterOctave>,
+    ) -> Instrument {
+        Instrument {
+            name: Cow::Borrowed(name),
+            transposition,
+            written_range,
+        }
+    }
+
+    /// Whether written notes differ from the concert pitches that sound.
+    #[inline]
+    pub fn is_transposing(&self) -> bool {
+        self.transposition != Interval::UNISON
+    }
+
+    /// The lowest and highest notes that sound, in concert pitch.
+    pub fn sounding_range(&self) -> RangeInclusive<LetterOctave> {
+        let start = self.written_to_concert(*self.written_range.start());
+        let end = self.written_to_concert(*self.written_range.end());
+        start..=end
+    }
+
+    /// The concert pitch that sounds when the given note is written.
+    #[inline]
+    pub fn written_to_concert(&self, written: LetterOctave) -> LetterOctave {
+        written + self.transposition
+    }
+
+    /// The note to write for the instrument to sound the given concert pitch.
+    #[inline]
+    pub fn concert_to_written(&self, concert: LetterOctave) -> LetterOctave {
+        concert - self.transposition
+    }
+}
+
+/// Shorthand for the written ranges of the catalogue.
+const fn range(
+    (low, low_octave): (Letter, i32),
+    (high, high_octave): (Letter, i32),
+) -> RangeInclusive<LetterOctave> {
+    RangeInclusive::new(
+        LetterOctave(low, low_octave),
+        LetterOctave(high, high_octave),
+    )
+}
+
+const OCTAVE_DOWN: Interval = Interval::octaves(-1);
+
+pub const VIOLIN: Instrument = Instrument::new(
+    "Violin",
+    Interval::UNISON,
+    range((Letter::G, 3), (Letter::A, 7)),
+);
+pub const VIOLA: Instrument = Instrument::new(
+    "Viola",
+    Interval::UNISON,
+    range((Letter::C, 3), (Letter::E, 6)),
+);
+pub const CELLO: Instrument = Instrument::new(
+    "Cello",
+    Interval::UNISON,
+    range((Letter::C, 2), (Letter::A, 5)),
+);
+/// Written an octave above concert pitch.
+pub const DOUBLE_BASS: Instrument = Instrument::new(
+    "Double Bass",
+    OCTAVE_DOWN,
+    range((Letter::E, 2), (Letter::C, 5)),
+);
+pub const FLUTE: Instrument = Instrument::new(
+    "Flute",
+    Interval::UNISON,
+    range((Letter::C, 4), (Letter::C, 7)),
+);
+pub const CLARINET_IN_BB: Instrument = Instrument::new(
+    "Clarinet in Bb",
+    Interval(-2),
+    range((Letter::E, 3), (Letter::C, 7)),
+);
+pub const ALTO_SAXOPHONE_IN_EB: Instrument = Instrument::new(
+    "Alto Saxophone in Eb",
+    Interval(-9),
+    range((Letter::Ash, 3), (Letter::Fsh, 6)),
+);
+pub const TRUMPET_IN_BB: Instrument = Instrument::new(
+    "Trumpet in Bb",
+    Interval(-2),
+    range((Letter::Fsh, 3), (Letter::D, 6)),
+);
+pub const HORN_IN_F: Instrument = Instrument::new(
+    "Horn in F",
+    Interval(-7),
+    range((Letter::Fsh, 2), (Letter::C, 6)),
+);
+/// Written an octave above concert pitch.
+pub const GUITAR: Instrument =
+    Instrument::new("Guitar", OCTAVE_DOWN, range((Letter::E, 3), (Letter::B, 6)));
+/// A four string bass guitar with 24 frets, written an octave above concert pitch.
+pub const BASS_GUITAR: Instrument = Instrument::new(
+    "Bass Guitar",
+    OCTAVE_DOWN,
+    range((Letter::E, 2), (Letter::G, 5)),
+);
+pub const SOPRANO: Instrument = Instrument::new(
+    "Soprano",
+    Interval::UNISON,
+    range((Letter::C, 4), (Letter::A, 5)),
+);
+pub const ALTO: Instrument = Instrument::new(
+    "Alto",
+    Interval::UNISON,
+    range((Letter::F, 3), (Letter::D, 5)),
+);
+/// Written in the octave treble clef, an octave above concert pitch.
+pub const TENOR: Instrument =
+    Instrument::new("Tenor", OCTAVE_DOWN, range((Letter::C, 4), (Letter::C, 6)));
+pub const BASS: Instrument = Instrument::new(
+    "Bass",
+    Interval::UNISON,
+    range((Letter::E, 2), (Letter::E, 4)),
+);
+
+/// Every instrument in the catalogue, from the strings to the voices.
+pub const INSTRUMENTS: &[Instrument] = &[
+    VIOLIN,
+    VIOLA,
+    CELLO,
+    DOUBLE_BASS,
+    FLUTE,
+    CLARINET_IN_BB,
+    ALTO_SAXOPHONE_IN_EB,
+    TRUMPET_IN_BB,
+    HORN_IN_F,
+    GUITAR,
+    BASS_GUITAR,
+    SOPRANO,
+    ALTO,
+    TENOR,
+    BASS,
+];
+
+/// The instrument in the catalogue with the given name, ignoring case.
+pub fn find(name: &str) -> Option<&'static Instrument> {
+    INSTRUMENTS
+        .iter()
+        .find(|instrument| instrument.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transposition() {
+        let clarinet = find("clarinet in bb").unwrap();
+        assert!(clarinet.is_transposing());
+        let written = LetterOctave(Letter::C, 5);
+        let concert = clarinet.written_to_concert(written);
+        assert_eq!(concert, LetterOctave(Letter::Bb, 4));
+        assert_eq!(clarinet.concert_to_written(concert), written);
+        assert_eq!(
+            clarinet.sounding_range(),
+            LetterOctave(Letter::D, 3)..=LetterOctave(Letter::Bb, 6)
+        );
+
+        let horn = LetterOctave(Letter::C, 4);
+        assert_eq!(
+            HORN_IN_F.written_to_concert(horn),
+            LetterOctave(Letter::F, 3)
+        );
+        let sax = LetterOctave(Letter::C, 5);
+        assert_eq!(
+            ALTO_SAXOPHONE_IN_EB.written_to_concert(sax),
+            LetterOctave(Letter::Eb, 4)
+        );
+        assert_eq!(
+            GUITAR.sounding_range(),
+            LetterOctave(Letter::E, 2)..=LetterOctave(Letter::B, 5)
+        );
+        assert!(!VIOLIN.is_transposing());
+        assert_eq!(VIOLIN.sounding_range(), VIOLIN.written_range);
+        assert_eq!(find("Kazoo"), None);
+    }
+
+    #[test]
+    fn catalogue() {
+        for instrument in INSTRUMENTS {
+            let range = &instrument.written_range;
+            assert!(range.start() < range.end(), "{}", instrument.name);
+            let sounding = instrument.sounding_range();
+            assert_eq!(
+                *sounding.end() - *sounding.start(),
+                *range.end() - *range.start()
+            );
+            assert_eq!(find(&instrument.name), Some(instrument));
+        }
+    }
+}
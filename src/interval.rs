@@ -0,0 +1,216 @@
+use super::{calc, Hz};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// The distance between two pitches in equal tempered semitones.
+///
+/// Intervals may be added to or subtracted from absolute pitches such as `LetterOctave` and `Step`,
+/// and are the result of subtracting one `LetterOctave` from another.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Interval(pub calc::Semitones);
+
+impl Interval {
+    pub const UNISON: Interval = Interval(0);
+    pub const MINOR_SECOND: Interval = Interval(1);
+    pub const MAJOR_SECOND: Interval = Interval(2);
+    pub const MINOR_THIRD: Interval = Interval(3);
+    pub const MAJOR_THIRD: Interval = Interval(4);
+    pub const PERFECT_FOURTH: Interval = Interval(5);
+    pub const TRITONE: Interval = Interval(6);
+    pub const PERFECT_FIFTH: Interval = Interval(7);
+    pub const MINOR_SIXTH: Interval = Interval(8);
+    pub const MAJOR_SIXTH: Interval = Interval(9);
+    pub const MINOR_SEVENTH: Interval = Interval(10);
+    pub const MAJOR_SEVENTH: Interval = Interval(11);
+    pub const OCTAVE: Interval = Interval(12);
+
+    /// An interval spanning the given number of octaves.
+    #[inline]
+    pub const fn octaves(octaves: i32) -> Interval {
+        Interval(octaves * 12)
+    }
+
+    /// Return the size of the interval in semitones.
+    #[inline]
+    pub const fn semitones(self) -> calc::Semitones {
+        let Interval(semitones) = self;
+        semitones
+    }
+
+    /// Return the size of the interval in cents.
+    #[inline]
+    pub fn cents(self) -> f32 {
+        self.semitones() as f32 * 100.0
+    }
+
+    /// The frequency ratio of the equal tempered interval.
+    #[inline]
+    pub fn ratio(self) -> Ratio {
+        Ratio::from_cents(self.cents())
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+    #[inline]
+    fn add(self, rhs: Interval) -> Interval {
+        Interval(self.semitones() + rhs.semitones())
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+    #[inline]
+    fn sub(self, rhs: Interval) -> Interval {
+        Interval(self.semitones() - rhs.semitones())
+    }
+}
+
+impl Mul<i32> for Interval {
+    type Output = Interval;
+    #[inline]
+    fn mul(self, rhs: i32) -> Interval {
+        Interval(self.semitones() * rhs)
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+    #[inline]
+    fn neg(self) -> Interval {
+        Interval(-self.semitones())
+    }
+}
+
+impl From<Interval> for Ratio {
+    fn from(interval: Interval) -> Ratio {
+        interval.ratio()
+    }
+}
+
+/// The ratio between two frequencies, e.g. `2.0` for an octave.
+///
+/// Ordered and hashed by the ratio with `f32::total_cmp`, so NaN sorts last and equals itself.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ratio(pub f32);
+
+impl Ratio {
+    pub const UNISON: Ratio = Ratio(1.0);
+    pub const OCTAVE: Ratio = Ratio(2.0);
+
+    /// The ratio between the frequency `to` and the frequency `from`.
+    #[inline]
+    pub fn between(from: Hz, to: Hz) -> Ratio {
+        Ratio(to.hz() / from.hz())
+    }
+
+    /// The ratio spanning the given number of cents.
+    #[inline]
+    pub fn from_cents(cents: f32) -> Ratio {
+        Ratio((cents / 1200.0).exp2())
+    }
+
+    /// Return the unit value of the ratio.
+    #[inline]
+    pub fn ratio(self) -> f32 {
+        let Ratio(ratio) = self;
+        ratio
+    }
+
+    /// Return the size of the ratio in cents.
+    #[inline]
+    pub fn cents(self) -> f32 {
+        self.ratio().log2() * 1200.0
+    }
+
+    /// Return the size of the ratio in (fractional) equal tempered semitones.
+    #[inline]
+    pub fn semitones(self) -> calc::Step {
+        self.ratio().log2() * 12.0
+    }
+
+    /// The closest equal tempered interval.
+    #[inline]
+    pub fn to_interval(self) -> Interval {
+        Interval(self.semitones().round() as calc::Semitones)
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Ratio;
+    #[inline]
+    fn mul(self, rhs: Ratio) -> Ratio {
+        Ratio(self.ratio() * rhs.ratio())
+    }
+}
+
+impl Div for Ratio {
+    type Output = Ratio;
+    #[inline]
+    fn div(self, rhs: Ratio) -> Ratio {
+        Ratio(self.ratio() / rhs.ratio())
+    }
+}
+
+impl PartialEq for Ratio {
+    #[inline]
+    fn eq(&self, other: &Ratio) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ratio {}
+
+impl PartialOrd for Ratio {
+    #[inline]
+    fn partial_cmp(&self, other: &Ratio) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    #[inline]
+    fn cmp(&self, other: &Ratio) -> Ordering {
+        self.ratio().total_cmp(&other.ratio())
+    }
+}
+
+impl Hash for Ratio {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ratio().to_bits().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Letter, LetterOctave, Step};
+
+    #[test]
+    fn letter_octave_intervals() {
+        let c4 = LetterOctave(Letter::C, 4);
+        assert_eq!(c4 + Interval::PERFECT_FIFTH, LetterOctave(Letter::G, 4));
+        assert_eq!(c4 - Interval::MINOR_SECOND, LetterOctave(Letter::B, 3));
+        assert_eq!(c4 + Interval::octaves(2), LetterOctave(Letter::C, 6));
+        assert_eq!(LetterOctave(Letter::A, 4) - c4, Interval::MAJOR_SIXTH);
+        assert_eq!(c4 - c4, Interval::UNISON);
+    }
+
+    #[test]
+    fn step_and_hz_intervals() {
+        assert_eq!(Step(60.5) + Interval::OCTAVE, Step(72.5));
+        assert_eq!(Letter::B + Interval::MAJOR_SECOND, Letter::Csh);
+        let a5 = Hz(440.0) * Interval::OCTAVE.ratio();
+        assert_eq!(a5, Hz(880.0));
+        assert_eq!(a5 / Ratio::OCTAVE, Hz(440.0));
+        let fifth = Ratio::between(Hz(440.0), Hz(660.0));
+        assert_eq!(fifth.to_interval(), Interval::PERFECT_FIFTH);
+        assert!((fifth.cents() - 701.955).abs() < 1e-3);
+    }
+}
@@ -1,9 +1,13 @@
 use self::Letter::{Ab, Ash, Bb, Csh, Db, Dsh, Eb, Fsh, Gb, Gsh, A, B, C, D, E, F, G};
 use crate::utils::modulo;
+use crate::{Interval, ParsePitchError};
 use num_traits::{FromPrimitive, PrimInt as Int, ToPrimitive};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 use rand::{distr::Distribution, Rng};
 
@@ -40,19 +44,24 @@ impl PartialOrd for Letter {
 
 impl Ord for Letter {
     fn cmp(&self, other: &Letter) -> Ordering {
-        let other_u8 = other.to_u8().unwrap();
-        self.to_u8().unwrap().cmp(&other_u8)
+        self.pitch_class().cmp(&other.pitch_class())
     }
 }
 
 impl PartialEq for Letter {
     fn eq(&self, other: &Letter) -> bool {
-        self.to_u8().unwrap() == other.to_u8().unwrap()
+        self.pitch_class() == other.pitch_class()
     }
 }
 
 impl Eq for Letter {}
 
+impl Hash for Letter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pitch_class().hash(state);
+    }
+}
+
 impl Letter {
     /// Returns whether or not the note would be a black key on a standard piano or keyboard.
     pub fn is_black_key(self) -> bool {
@@ -62,77 +71,146 @@ impl Letter {
             C | D | E | F | G | A | B => false,
         }
     }
+
+    /// The pitch class of the letter, i.e. the number of semitones above `C` (in the range `0..12`).
+    ///
+    /// Enharmonic letters (e.g. `Csh` and `Db`) share the same pitch class.
+    #[inline]
+    pub const fn pitch_class(self) -> u8 {
+        match self {
+            C => 0,
+            Csh | Db => 1,
+            D => 2,
+            Dsh | Eb => 3,
+            E => 4,
+            F => 5,
+            Fsh | Gb => 6,
+            G => 7,
+            Gsh | Ab => 8,
+            A => 9,
+            Ash | Bb => 10,
+            B => 11,
+        }
+    }
+
+    /// The letter for the given pitch class, wrapping values outside of the range `0..12`.
+    ///
+    /// Black keys are always represented by their sharp variant.
+    #[inline]
+    pub const fn from_pitch_class(pitch_class: i64) -> Letter {
+        match pitch_class.rem_euclid(TOTAL_LETTERS as i64) {
+            0 => C,
+            1 => Csh,
+            2 => D,
+            3 => Dsh,
+            4 => E,
+            5 => F,
+            6 => Fsh,
+            7 => G,
+            8 => Gsh,
+            9 => A,
+            10 => Ash,
+            _ => B,
+        }
+    }
+
+    /// The conventional name of the letter, e.g. `"C#"` or `"Db"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            C => "C",
+            Csh => "C#",
+            Db => "Db",
+            D => "D",
+            Dsh => "D#",
+            Eb => "Eb",
+            E => "E",
+            F => "F",
+            Fsh => "F#",
+            Gb => "Gb",
+            G => "G",
+            Gsh => "G#",
+            Ab => "Ab",
+            A => "A",
+            Ash => "A#",
+            Bb => "Bb",
+            B => "B",
+        }
+    }
+
+    /// Parse a letter name such as `"C"`, `"c#"`, `"Db"` or `"Csh"` from the start of `s`.
+    ///
+    /// Returns the letter, the number of octaves by which the spelling crosses the boundary
+    /// between B and C (`-1` for `"Cb"`, `1` for `"B#"`, otherwise `0`) and the rest of `s`.
+    pub(crate) fn parse_prefix(s: &str) -> Option<(Letter, i32, &str)> {
+        let mut chars = s.chars();
+        let natural = match chars.next()?.to_ascii_uppercase() {
+            'C' => 0,
+            'D' => 2,
+            'E' => 4,
+            'F' => 5,
+            'G' => 7,
+            'A' => 9,
+            'B' => 11,
+            _ => return None,
+        };
+        let rest = chars.as_str();
+        let (accidental, rest) = if let Some(rest) = rest.strip_prefix("sh") {
+            (1, rest)
+        } else if let Some(rest) = rest.strip_prefix(['#', '♯']) {
+            (1, rest)
+        } else if let Some(rest) = rest.strip_prefix(['b', '♭']) {
+            (-1, rest)
+        } else {
+            (0, rest)
+        };
+        let pitch_class: i32 = natural + accidental;
+        let letter = match (natural, accidental) {
+            (2, -1) => Db,
+            (4, -1) => Eb,
+            (7, -1) => Gb,
+            (9, -1) => Ab,
+            (11, -1) => Bb,
+            _ => Letter::from_pitch_class(pitch_class as i64),
+        };
+        Some((letter, pitch_class.div_euclid(12), rest))
+    }
+}
+
+impl fmt::Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Letter {
+    type Err = ParsePitchError;
+    fn from_str(s: &str) -> Result<Letter, ParsePitchError> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Err(ParsePitchError::Empty);
+        }
+        match Letter::parse_prefix(trimmed) {
+            Some((letter, _, "")) => Ok(letter),
+            _ => Err(ParsePitchError::invalid(s)),
+        }
+    }
 }
 
 impl FromPrimitive for Letter {
     fn from_i64(n: i64) -> Option<Letter> {
-        match modulo(n, 12) {
-            0 => Some(C),
-            1 => Some(Csh),
-            2 => Some(D),
-            3 => Some(Dsh),
-            4 => Some(E),
-            5 => Some(F),
-            6 => Some(Fsh),
-            7 => Some(G),
-            8 => Some(Gsh),
-            9 => Some(A),
-            10 => Some(Ash),
-            11 => Some(B),
-            _ => None,
-        }
+        Some(Letter::from_pitch_class(n))
     }
     fn from_u64(n: u64) -> Option<Letter> {
-        match modulo(n, 12) {
-            0 => Some(C),
-            1 => Some(Csh),
-            2 => Some(D),
-            3 => Some(Dsh),
-            4 => Some(E),
-            5 => Some(F),
-            6 => Some(Fsh),
-            7 => Some(G),
-            8 => Some(Gsh),
-            9 => Some(A),
-            10 => Some(Ash),
-            11 => Some(B),
-            _ => None,
-        }
+        Some(Letter::from_pitch_class(modulo(n, 12) as i64))
     }
 }
 
 impl ToPrimitive for Letter {
     fn to_i64(&self) -> Option<i64> {
-        match *self {
-            C => Some(0),
-            Csh | Db => Some(1),
-            D => Some(2),
-            Dsh | Eb => Some(3),
-            E => Some(4),
-            F => Some(5),
-            Fsh | Gb => Some(6),
-            G => Some(7),
-            Gsh | Ab => Some(8),
-            A => Some(9),
-            Ash | Bb => Some(10),
-            B => Some(11),
-        }
+        Some(self.pitch_class() as i64)
     }
     fn to_u64(&self) -> Option<u64> {
-        match *self {
-            C => Some(0),
-            Csh | Db => Some(1),
-            D => Some(2),
-            Dsh | Eb => Some(3),
-            E => Some(4),
-            F => Some(5),
-            Fsh | Gb => Some(6),
-            G => Some(7),
-            Gsh | Ab => Some(8),
-            A => Some(9),
-            Ash | Bb => Some(10),
-            B => Some(11),
-        }
+        Some(self.pitch_class() as u64)
     }
 }
 
@@ -183,3 +261,19 @@ impl ::std::ops::Sub for Letter {
         self - rhs.to_i16().unwrap()
     }
 }
+
+impl ::std::ops::Add<Interval> for Letter {
+    type Output = Letter;
+    #[inline]
+    fn add(self, rhs: Interval) -> Letter {
+        self + rhs.semitones()
+    }
+}
+
+impl ::std::ops::Sub<Interval> for Letter {
+    type Output = Letter;
+    #[inline]
+    fn sub(self, rhs: Interval) -> Letter {
+        self - rhs.semitones()
+    }
+}
@@ -1,42 +1,88 @@
 use super::{
-    calc, hz_from_letter_octave, mel_from_letter_octave, perc_from_letter_octave,
-    scaled_perc_from_letter_octave, step_from_letter_octave, Hz, Letter, Mel, Perc, ScaleWeight,
-    ScaledPerc, Step, DEFAULT_SCALE_WEIGHT,
+    bark_from_hz, calc, erb_from_hz, hz_from_letter_octave, hz_from_semitones,
+    letter_octave_from_semitones, mel_from_letter_octave, perc_from_letter_octave,
+    scaled_perc_from_letter_octave, semitones_from_letter_octave, step_from_letter_octave,
+    try_step_from_letter_octave, Bark, Erb, Hz, Interval, Letter, Mel, ParsePitchError, Perc,
+    Pitch, ScaleWeight, ScaledPerc, Step, DEFAULT_SCALE_WEIGHT,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
 
 pub type Octave = i32;
 
 /// Pitch representation in the form of a frequency (hz).
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde::LetterOctaveRepr"))]
 pub struct LetterOctave(pub Letter, pub Octave);
 
 impl LetterOctave {
     /// Return the value as (Letter, Octave).
     #[inline]
-    pub fn letter_octave(self) -> (Letter, Octave) {
+    pub const fn letter_octave(self) -> (Letter, Octave) {
         let LetterOctave(letter, octave) = self;
         (letter, octave)
     }
 
     /// Return just the Letter.
     #[inline]
-    pub fn letter(self) -> Letter {
+    pub const fn letter(self) -> Letter {
         let LetterOctave(letter, _) = self;
         letter
     }
 
     /// Return just the octave.
     #[inline]
-    pub fn octave(self) -> Octave {
+    pub const fn octave(self) -> Octave {
         let LetterOctave(_, octave) = self;
         octave
     }
 
+    /// The LetterOctave for the given number of whole MIDI steps.
+    #[inline]
+    pub const fn from_semitones(step: calc::Semitones) -> LetterOctave {
+        let (letter, octave) = letter_octave_from_semitones(step);
+        LetterOctave(letter, octave)
+    }
+
+    /// Convert to the number of whole MIDI steps.
+    #[inline]
+    pub const fn semitones(self) -> calc::Semitones {
+        let LetterOctave(letter, octave) = self;
+        semitones_from_letter_octave(letter, octave)
+    }
+
+    /// Parse a note name such as `"A4"`, `"C#-1"` or `"Bb3"` from the start of `s`, returning the
+    /// note and the rest of `s`.
+    ///
+    /// Returns `None` if the octave lies outside the range of representable pitches.
+    pub(crate) fn parse_prefix(s: &str) -> Option<(LetterOctave, &str)> {
+        let (letter, octave_shift, rest) = Letter::parse_prefix(s)?;
+        let sign = usize::from(rest.starts_with('-'));
+        let digits = rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        let (octave, rest) = rest.split_at(sign + digits);
+        let octave = octave.parse::<Octave>().ok()?.checked_add(octave_shift)?;
+        try_step_from_letter_octave(letter, octave).ok()?;
+        Some((LetterOctave(letter, octave), rest))
+    }
+
+    /// Convert to a Hz with the equivalent pitch.
+    ///
+    /// Unlike `to_hz` this may be evaluated at compile time, e.g.
+    /// `const A4: Hz = LetterOctave(Letter::A, 4).to_hz_const();`.
+    #[inline]
+    pub const fn to_hz_const(self) -> Hz {
+        Hz(hz_from_semitones(self.semitones()))
+    }
+
     /// Convert to the unit value of Hz with the equivalent pitch.
     #[inline]
     pub fn hz(self) -> calc::Hz {
@@ -45,9 +91,10 @@ impl LetterOctave {
     }
 
     /// Convert to a Hz with the equivalent pitch.
+    #[deprecated(note = "use Pitch::to_hz")]
     #[inline]
     pub fn to_hz(self) -> Hz {
-        Hz(self.hz())
+        Pitch::to_hz(self)
     }
 
     /// Convert to the unit value of a Mel with equivalent pitch.
@@ -58,9 +105,36 @@ impl LetterOctave {
     }
 
     /// Convert to a Mel struct.
+    #[deprecated(note = "use Pitch::to_mel")]
     #[inline]
     pub fn to_mel(self) -> Mel {
-        Mel(self.mel())
+        Pitch::to_mel(self)
+    }
+
+    /// Convert to the unit value of a Bark.
+    #[inline]
+    pub fn bark(self) -> calc::Bark {
+        bark_from_hz(self.hz())
+    }
+
+    /// Convert to a Bark struct.
+    #[deprecated(note = "use Pitch::to_bark")]
+    #[inline]
+    pub fn to_bark(self) -> Bark {
+        Pitch::to_bark(self)
+    }
+
+    /// Convert to the unit value of an Erb.
+    #[inline]
+    pub fn erb(self) -> calc::Erb {
+        erb_from_hz(self.hz())
+    }
+
+    /// Convert to an Erb struct.
+    #[deprecated(note = "use Pitch::to_erb")]
+    #[inline]
+    pub fn to_erb(self) -> Erb {
+        Pitch::to_erb(self)
     }
 
     /// Convert to the unit value of a Perc.
@@ -71,9 +145,10 @@ impl LetterOctave {
     }
 
     /// Convert to a percentage of the human hearing range.
+    #[deprecated(note = "use Pitch::to_perc")]
     #[inline]
     pub fn to_perc(self) -> Perc {
-        Perc(self.perc())
+        Pitch::to_perc(self)
     }
 
     /// Convert to a scaled percentage of the human hearing range with a given weight.
@@ -96,62 +171,48 @@ impl LetterOctave {
     }
 
     /// Convert to a scaled percentage of the human hearing range.
+    #[deprecated(note = "use Pitch::to_scaled_perc")]
     #[inline]
     pub fn to_scaled_perc(self) -> ScaledPerc {
-        self.to_scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+        Pitch::to_scaled_perc(self)
     }
 
     /// Convert to the unit value of a Step.
     #[inline]
-    pub fn step(self) -> calc::Step {
+    pub const fn step(self) -> calc::Step {
         let LetterOctave(letter, octave) = self;
         step_from_letter_octave(letter, octave)
     }
 
     /// Convert to a floating point MIDI-esque Step.
+    #[deprecated(note = "use Pitch::to_step")]
     #[inline]
-    pub fn to_step(self) -> Step {
+    pub const fn to_step(self) -> Step {
         Step(self.step())
     }
 }
 
-impl Add for LetterOctave {
-    type Output = LetterOctave;
-    #[inline]
-    fn add(self, rhs: LetterOctave) -> LetterOctave {
-        (self.to_step() + rhs.to_step()).to_letter_octave()
-    }
-}
-
-impl Sub for LetterOctave {
-    type Output = LetterOctave;
-    #[inline]
-    fn sub(self, rhs: LetterOctave) -> LetterOctave {
-        (self.to_step() - rhs.to_step()).to_letter_octave()
-    }
-}
-
-impl Mul for LetterOctave {
+impl Add<Interval> for LetterOctave {
     type Output = LetterOctave;
     #[inline]
-    fn mul(self, rhs: LetterOctave) -> LetterOctave {
-        (self.to_step() * rhs.to_step()).to_letter_octave()
+    fn add(self, rhs: Interval) -> LetterOctave {
+        LetterOctave::from_semitones(self.semitones() + rhs.semitones())
     }
 }
 
-impl Div for LetterOctave {
+impl Sub<Interval> for LetterOctave {
     type Output = LetterOctave;
     #[inline]
-    fn div(self, rhs: LetterOctave) -> LetterOctave {
-        (self.to_step() / rhs.to_step()).to_letter_octave()
+    fn sub(self, rhs: Interval) -> LetterOctave {
+        LetterOctave::from_semitones(self.semitones() - rhs.semitones())
     }
 }
 
-impl Rem for LetterOctave {
-    type Output = LetterOctave;
+impl Sub for LetterOctave {
+    type Output = Interval;
     #[inline]
-    fn rem(self, rhs: LetterOctave) -> LetterOctave {
-        (self.to_step() % rhs.to_step()).to_letter_octave()
+    fn sub(self, rhs: LetterOctave) -> Interval {
+        Interval(self.semitones() - rhs.semitones())
     }
 }
 
@@ -159,7 +220,7 @@ impl Neg for LetterOctave {
     type Output = LetterOctave;
     #[inline]
     fn neg(self) -> LetterOctave {
-        (-self.to_step()).to_letter_octave()
+        LetterOctave::from(-Step(self.step()))
     }
 }
 
@@ -174,6 +235,15 @@ impl PartialEq for LetterOctave {
 
 impl Eq for LetterOctave {}
 
+impl Hash for LetterOctave {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let LetterOctave(letter, octave) = *self;
+        letter.hash(state);
+        octave.hash(state);
+    }
+}
+
 impl PartialOrd for LetterOctave {
     #[inline]
     fn partial_cmp(&self, other: &LetterOctave) -> Option<Ordering> {
@@ -192,3 +262,39 @@ impl Ord for LetterOctave {
         }
     }
 }
+
+impl fmt::Display for LetterOctave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let LetterOctave(letter, octave) = *self;
+        f.pad(&format!("{}{}", letter, octave))
+    }
+}
+
+impl FromStr for LetterOctave {
+    type Err = ParsePitchError;
+    fn from_str(s: &str) -> Result<LetterOctave, ParsePitchError> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Err(ParsePitchError::Empty);
+        }
+        match LetterOctave::parse_prefix(trimmed) {
+            Some((letter_octave, "")) => Ok(letter_octave),
+            _ => Err(ParsePitchError::invalid(s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extreme_octaves() {
+        assert_eq!("B#-2".parse(), Ok(LetterOctave(Letter::C, -1)));
+        assert_eq!("Cb0".parse(), Ok(LetterOctave(Letter::B, -1)));
+        for invalid in ["B#2147483647", "Cb-2147483648", "C999999999", "A-999999999"] {
+            let error = ParsePitchError::invalid(invalid);
+            assert_eq!(invalid.parse::<LetterOctave>(), Err(error), "{}", invalid);
+        }
+    }
+}
@@ -5,33 +5,78 @@
 //!  Created by Mitchell Nordine at 11:26PM on November 02, 2014.
 //!
 
+pub use self::bark::{Bark, BarkScale, DEFAULT_BARK_SCALE};
 pub use self::calc::{
-    difference_in_semitones, hz_from_letter_octave, hz_from_mel, hz_from_perc, hz_from_scaled_perc,
-    hz_from_step, letter_octave_from_hz, letter_octave_from_mel, letter_octave_from_perc,
-    letter_octave_from_scaled_perc, letter_octave_from_step, mel_from_hz, mel_from_letter_octave,
-    mel_from_perc, mel_from_scaled_perc, mel_from_step, perc_from_hz, perc_from_letter_octave,
-    perc_from_mel, perc_from_scaled_perc, perc_from_step, scaled_perc_from_hz,
-    scaled_perc_from_letter_octave, scaled_perc_from_mel, scaled_perc_from_perc,
-    scaled_perc_from_step, step_from_hz, step_from_letter_octave, step_from_mel, step_from_perc,
-    step_from_scaled_perc,
+    bark_from_hz, bark_from_hz_with_scale, difference_in_semitones, erb_bandwidth_from_hz,
+    erb_from_hz, hz_from_bark, hz_from_bark_with_scale, hz_from_erb, hz_from_letter_octave,
+    hz_from_mel, hz_from_mel_with_scale, hz_from_perc, hz_from_perc_in_range, hz_from_scaled_perc,
+    hz_from_scaled_perc_in_range, hz_from_scaled_perc_with_curve, hz_from_semitones, hz_from_step,
+    hz_from_step_with_tuning, letter_octave_from_hz, letter_octave_from_mel,
+    letter_octave_from_perc, letter_octave_from_scaled_perc, letter_octave_from_semitones,
+    letter_octave_from_step, mel_from_hz, mel_from_hz_with_scale, mel_from_letter_octave,
+    mel_from_perc, mel_from_scaled_perc, mel_from_step, perc_from_hz, perc_from_hz_in_range,
+    perc_from_letter_octave, perc_from_mel, perc_from_scaled_perc,
+    perc_from_scaled_perc_with_curve, perc_from_step, scaled_perc_from_hz,
+    scaled_perc_from_hz_in_range, scaled_perc_from_hz_with_curve, scaled_perc_from_letter_octave,
+    scaled_perc_from_mel, scaled_perc_from_perc, scaled_perc_from_perc_with_curve,
+    scaled_perc_from_step, semitones_from_letter_octave, step_from_hz, step_from_hz_with_tuning,
+    step_from_letter_octave, step_from_mel, step_from_perc, step_from_scaled_perc,
+    try_bark_from_hz, try_erb_from_hz, try_hz_from_bark, try_hz_from_erb,
+    try_hz_from_letter_octave, try_hz_from_mel, try_hz_from_perc, try_hz_from_scaled_perc,
+    try_hz_from_step, try_letter_octave_from_hz, try_letter_octave_from_mel,
+    try_letter_octave_from_perc, try_letter_octave_from_scaled_perc, try_letter_octave_from_step,
+    try_mel_from_hz, try_mel_from_letter_octave, try_mel_from_perc, try_mel_from_scaled_perc,
+    try_mel_from_step, try_perc_from_hz, try_perc_from_letter_octave, try_perc_from_mel,
+    try_perc_from_scaled_perc, try_perc_from_step, try_scaled_perc_from_hz,
+    try_scaled_perc_from_letter_octave, try_scaled_perc_from_mel, try_scaled_perc_from_perc,
+    try_scaled_perc_from_step, try_step_from_hz, try_step_from_letter_octave, try_step_from_mel,
+    try_step_from_perc, try_step_from_scaled_perc,
 };
-pub use self::hz::Hz;
+pub use self::calc::{DEFAULT_TUNING, MIDI_HZ, TOTAL_MIDI_NOTES};
+pub use self::curve::{Curve, DefaultCurve};
+pub use self::erb::Erb;
+pub use self::error::{ParsePitchError, PitchError};
 pub use self::hz::MAX as MAX_HZ;
 pub use self::hz::MIN as MIN_HZ;
+pub use self::hz::{BinMapper, FrequencyRange, Hz, DEFAULT_FREQUENCY_RANGE};
+pub use self::interval::{Interval, Ratio};
 pub use self::letter::{Letter, TOTAL_LETTERS};
 pub use self::letter_octave::{LetterOctave, Octave};
-pub use self::mel::Mel;
+pub use self::mel::{Mel, MelFormula, MelScale, DEFAULT_MEL_SCALE};
 pub use self::perc::Perc;
+pub use self::pitch::{AnyPitch, Pitch};
 pub use self::scaled_perc::{ScaleWeight, ScaledPerc, DEFAULT_SCALE_WEIGHT};
 pub use self::step::Step;
 
+pub mod bark;
+pub mod batch;
 pub mod calc;
+pub mod chroma;
+pub mod contour;
 pub mod convert;
+pub mod cqt;
+pub mod curve;
+pub mod erb;
+pub mod error;
+pub mod fast;
+pub mod filterbank;
+pub mod harmonic;
 pub mod hz;
+pub mod instrument;
+pub mod interval;
 pub mod letter;
 pub mod letter_octave;
 pub mod mel;
+pub mod normalized;
 pub mod perc;
+pub mod pitch;
+pub mod random;
 pub mod scaled_perc;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod step;
+pub mod stretch;
 pub mod utils;
+pub mod voice_leading;
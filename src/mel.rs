@@ -1,44 +1,142 @@
 use super::{
-    calc, hz_from_mel, letter_octave_from_mel, perc_from_mel, scaled_perc_from_mel, step_from_mel,
-    Hz, Letter, LetterOctave, Octave, Perc, ScaleWeight, ScaledPerc, Step, DEFAULT_SCALE_WEIGHT,
+    bark_from_hz, calc, erb_from_hz, hz_from_mel_with_scale, letter_octave_from_hz,
+    mel_from_hz_with_scale, perc_from_hz, scaled_perc_from_hz, step_from_hz, Bark, Erb, Hz, Letter,
+    LetterOctave, Octave, Perc, Pitch, ScaleWeight, ScaledPerc, Step, DEFAULT_SCALE_WEIGHT,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
+
+/// The formula used to convert between frequency in hz and mels.
+///
+/// The scale of a `Mel` value is given by the matching `MelFormula` type, e.g. `Slaney`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MelScale {
+    /// `2595 * log10(1 + hz / 700)` as described by O'Shaughnessy (1987).
+    ///
+    /// This is the formula used by librosa when `htk=True`.
+    OShaughnessy,
+    /// `1127 * ln(1 + hz / 700)` as used by the Hidden Markov Model Toolkit (HTK).
+    Htk,
+    /// Linear below 1khz and logarithmic above, as used by Slaney's Auditory Toolbox.
+    ///
+    /// This is librosa's default mel scale, where 1khz is 15 mels.
+    Slaney,
+    /// `1000 / log10(2) * log10(1 + hz / 1000)` as described by Fant (1968).
+    Fant,
+}
+
+/// The mel scale used by the conversions that do not take a `MelScale`, and by `Mel` by default.
+pub const DEFAULT_MEL_SCALE: MelScale = MelScale::OShaughnessy;
+
+/// A mel scale known at compile time, for use as the scale of a `Mel`.
+pub trait MelFormula: Copy + Default {
+    /// The formula used to convert between hz and mels on this scale.
+    const SCALE: MelScale;
+}
+
+/// The O'Shaughnessy mel scale, used by `Mel` by default. See `MelScale::OShaughnessy`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OShaughnessy;
+
+/// The HTK mel scale. See `MelScale::Htk`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Htk;
+
+/// The Slaney mel scale, librosa's default. See `MelScale::Slaney`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Slaney;
+
+/// The Fant mel scale. See `MelScale::Fant`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fant;
+
+impl MelFormula for OShaughnessy {
+    const SCALE: MelScale = MelScale::OShaughnessy;
+}
+
+impl MelFormula for Htk {
+    const SCALE: MelScale = MelScale::Htk;
+}
+
+impl MelFormula for Slaney {
+    const SCALE: MelScale = MelScale::Slaney;
+}
+
+impl MelFormula for Fant {
+    const SCALE: MelScale = MelScale::Fant;
+}
 
 /// Mel value representation
 ///     - based on the Mel scale coined by Stevens, Volkmann and Newman in 1937.
+///
+/// The scale on which the value is measured is part of the type, e.g. `Mel(15.0, Slaney)` is 1khz
+/// on the Slaney scale and `Mel<Htk>` is a mel on the HTK scale. Every conversion of a `Mel`
+/// uses its scale. `Mel::new` creates a mel on the default O'Shaughnessy scale.
+///
+/// Ordered and hashed by the mel value with `f32::total_cmp`, so NaN sorts last and equals itself.
+/// Mels are only compared with mels on the same scale.
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Mel(pub calc::Mel);
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "crate::serde::MelRepr",
+        into = "crate::serde::MelRepr",
+        bound = "S: MelFormula"
+    )
+)]
+pub struct Mel<S = OShaughnessy>(pub calc::Mel, pub S);
 
 impl Mel {
+    /// A mel on the default O'Shaughnessy scale.
+    #[inline]
+    pub const fn new(mel: calc::Mel) -> Mel {
+        Mel(mel, OShaughnessy)
+    }
+}
+
+impl<S: MelFormula> Mel<S> {
     /// Return the unit value of the Mel struct.
     #[inline]
     pub fn mel(self) -> calc::Mel {
-        let Mel(mel) = self;
+        let Mel(mel, _) = self;
         mel
     }
 
+    /// The mel scale on which the value is measured.
+    #[inline]
+    pub fn scale(self) -> MelScale {
+        S::SCALE
+    }
+
+    /// Convert to the equivalent pitch on another mel scale.
+    #[inline]
+    pub fn to_scale<T: MelFormula>(self) -> Mel<T> {
+        Mel(mel_from_hz_with_scale(self.hz(), T::SCALE), T::default())
+    }
+
     /// Convert to hz.
     #[inline]
     pub fn hz(self) -> calc::Hz {
-        hz_from_mel(self.mel())
+        hz_from_mel_with_scale(self.mel(), S::SCALE)
     }
 
     /// Convert to a Hz struct.
+    #[deprecated(note = "use Pitch::to_hz")]
     #[inline]
     pub fn to_hz(self) -> Hz {
-        Hz(self.hz())
+        Pitch::to_hz(self)
     }
 
     /// Convert to (Letter, Octave) tuple.
     #[inline]
     pub fn letter_octave(self) -> (Letter, Octave) {
-        letter_octave_from_mel(self.mel())
+        letter_octave_from_hz(self.hz())
     }
-
     /// Convert to Letter.
     #[inline]
     pub fn letter(self) -> Letter {
@@ -54,28 +152,55 @@ impl Mel {
     }
 
     /// Convert to LetterOctave struct with the closest pitch.
+    #[deprecated(note = "use Pitch::to_letter_octave")]
     #[inline]
     pub fn to_letter_octave(self) -> LetterOctave {
-        let (letter, octave) = self.letter_octave();
-        LetterOctave(letter, octave)
+        Pitch::to_letter_octave(self)
+    }
+
+    /// Convert to the unit value of a Bark.
+    #[inline]
+    pub fn bark(self) -> calc::Bark {
+        bark_from_hz(self.hz())
+    }
+
+    /// Convert to a Bark struct.
+    #[deprecated(note = "use Pitch::to_bark")]
+    #[inline]
+    pub fn to_bark(self) -> Bark {
+        Pitch::to_bark(self)
+    }
+
+    /// Convert to the unit value of an Erb.
+    #[inline]
+    pub fn erb(self) -> calc::Erb {
+        erb_from_hz(self.hz())
+    }
+
+    /// Convert to an Erb struct.
+    #[deprecated(note = "use Pitch::to_erb")]
+    #[inline]
+    pub fn to_erb(self) -> Erb {
+        Pitch::to_erb(self)
     }
 
     /// Convert to a percentage of the human hearing range.
     #[inline]
     pub fn perc(self) -> calc::Perc {
-        perc_from_mel(self.mel())
+        perc_from_hz(self.hz())
     }
 
     /// Convert to a Perc struct.
+    #[deprecated(note = "use Pitch::to_perc")]
     #[inline]
     pub fn to_perc(self) -> Perc {
-        Perc(self.perc())
+        Pitch::to_perc(self)
     }
 
     /// Convert to a scaled percentage of the human hearing range with a given weight.
     #[inline]
     pub fn scaled_perc_with_weight(self, weight: ScaleWeight) -> calc::Perc {
-        scaled_perc_from_mel(self.mel(), weight)
+        scaled_perc_from_hz(self.hz(), weight)
     }
 
     /// Convert to a scaled percentage of the human hearing range.
@@ -91,84 +216,111 @@ impl Mel {
     }
 
     /// Convert to a scaled percentage of the human hearing range.
+    #[deprecated(note = "use Pitch::to_scaled_perc")]
     #[inline]
     pub fn to_scaled_perc(self) -> ScaledPerc {
-        self.to_scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+        Pitch::to_scaled_perc(self)
     }
 
     /// Convert to the unit value of a Step.
     #[inline]
     pub fn step(self) -> calc::Step {
-        step_from_mel(self.mel())
+        step_from_hz(self.hz())
     }
 
     /// Convert to a Step struct.
+    #[deprecated(note = "use Pitch::to_step")]
     #[inline]
     pub fn to_step(self) -> Step {
-        Step(self.step())
+        Pitch::to_step(self)
     }
 }
 
-impl Add for Mel {
-    type Output = Mel;
+impl<S: MelFormula> Add for Mel<S> {
+    type Output = Mel<S>;
     #[inline]
-    fn add(self, rhs: Mel) -> Mel {
-        Mel(self.mel() + rhs.mel())
+    fn add(self, rhs: Mel<S>) -> Mel<S> {
+        Mel(self.mel() + rhs.mel(), self.1)
     }
 }
 
-impl Sub for Mel {
-    type Output = Mel;
+impl<S: MelFormula> Sub for Mel<S> {
+    type Output = Mel<S>;
     #[inline]
-    fn sub(self, rhs: Mel) -> Mel {
-        Mel(self.mel() - rhs.mel())
+    fn sub(self, rhs: Mel<S>) -> Mel<S> {
+        Mel(self.mel() - rhs.mel(), self.1)
     }
 }
 
-impl Mul for Mel {
-    type Output = Mel;
+impl<S: MelFormula> Neg for Mel<S> {
+    type Output = Mel<S>;
     #[inline]
-    fn mul(self, rhs: Mel) -> Mel {
-        Mel(self.mel() * rhs.mel())
+    fn neg(self) -> Mel<S> {
+        Mel(-self.mel(), self.1)
     }
 }
 
-impl Div for Mel {
-    type Output = Mel;
+impl<S: MelFormula> PartialEq for Mel<S> {
     #[inline]
-    fn div(self, rhs: Mel) -> Mel {
-        Mel(self.mel() / rhs.mel())
+    fn eq(&self, other: &Mel<S>) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-impl Rem for Mel {
-    type Output = Mel;
+impl<S: MelFormula> Eq for Mel<S> {}
+
+impl<S: MelFormula> PartialOrd for Mel<S> {
     #[inline]
-    fn rem(self, rhs: Mel) -> Mel {
-        Mel(self.mel() % rhs.mel())
+    fn partial_cmp(&self, other: &Mel<S>) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl Neg for Mel {
-    type Output = Mel;
+impl<S: MelFormula> Ord for Mel<S> {
     #[inline]
-    fn neg(self) -> Mel {
-        Mel(-self.mel())
+    fn cmp(&self, other: &Mel<S>) -> Ordering {
+        self.mel().total_cmp(&other.mel())
     }
 }
 
-impl PartialEq for Mel {
+impl<S: MelFormula> Hash for Mel<S> {
     #[inline]
-    fn eq(&self, other: &Mel) -> bool {
-        self.mel() == other.mel()
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.mel().to_bits().hash(state);
     }
 }
 
-impl Eq for Mel {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hz, LetterOctave, Pitch};
 
-impl PartialOrd for Mel {
-    #[inline]
-    fn partial_cmp(&self, other: &Mel) -> Option<Ordering> {
-        self.mel().partial_cmp(&other.mel())
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-2, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn scales() {
+        // librosa.mel_to_hz(6.6) with its default Slaney scale.
+        let slaney = Mel(6.6, Slaney);
+        assert_close(slaney.hz(), 440.0);
+        assert_close(slaney.step(), 69.0);
+        assert_eq!(Pitch::to_letter_octave(slaney), LetterOctave(Letter::A, 4));
+        assert_eq!(slaney.scale(), MelScale::Slaney);
+
+        let hz = Hz(4000.0);
+        assert_close(hz.to_mel_with_scale(Slaney).mel(), 35.163_76);
+        assert_close(hz.to_mel_with_scale(Htk).mel(), 2146.076);
+        assert_close(Mel::<Fant>::from_hz(hz).hz(), 4000.0);
+        assert_close(
+            Mel::from(hz).mel(),
+            hz.to_mel_with_scale(OShaughnessy).mel(),
+        );
+
+        let htk: Mel<Htk> = slaney.to_scale();
+        assert_close(htk.hz(), 440.0);
+        assert_close(htk.mel(), 549.64);
+        assert!(Mel(1000.0, Slaney) > Mel(15.0, Slaney));
+        assert_close((Mel(10.0, Slaney) + Mel(5.0, Slaney)).hz(), 1000.0);
     }
 }
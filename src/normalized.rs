@@ -0,0 +1,243 @@
+//! Pitches normalized to `0..1`, as used for the automation of plugin parameters.
+//!
+//! A `NormalizedPitch` maps `0.0` and `1.0` to the ends of a `PitchRange`, logarithmically in
+//! frequency (i.e. linearly in steps), so that equal distances always span equal intervals.
+
+use crate::{calc, FrequencyRange, Hz, LetterOctave, ParsePitchError, PitchError, Step};
+use std::fmt;
+
+/// The range of pitches spanned by a `NormalizedPitch`.
+///
+/// The representation of the range also determines how a `NormalizedPitch` is displayed: as a
+/// frequency, e.g. `"1.25 kHz"`, for `Frequency`, and as a note, e.g. `"A4 +3¢"`, for `Steps`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PitchRange {
+    Frequency(FrequencyRange),
+    Steps(Step, Step),
+}
+
+/// A pitch within a `PitchRange`, represented by a value in `0..1`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NormalizedPitch {
+    value: f64,
+    range: PitchRange,
+}
+
+impl PitchRange {
+    /// A range of steps from `low` to `high`.
+    ///
+    /// Returns an error unless both are finite and `low < high`.
+    pub fn steps(low: Step, high: Step) -> Result<PitchRange, PitchError> {
+        if low.step().is_finite() && high.step().is_finite() && low < high {
+            Ok(PitchRange::Steps(low, high))
+        } else {
+            Err(PitchError::InvalidStepRange(low.step(), high.step()))
+        }
+    }
+
+    /// The lowest pitch in the range.
+    #[inline]
+    pub fn low(self) -> Step {
+        match self {
+            PitchRange::Frequency(range) => Step::from(Hz(range.min())),
+            PitchRange::Steps(low, _) => low,
+        }
+    }
+
+    /// The highest pitch in the range.
+    #[inline]
+    pub fn high(self) -> Step {
+        match self {
+            PitchRange::Frequency(range) => Step::from(Hz(range.max())),
+            PitchRange::Steps(_, high) => high,
+        }
+    }
+}
+
+impl From<FrequencyRange> for PitchRange {
+    fn from(range: FrequencyRange) -> PitchRange {
+        PitchRange::Frequency(range)
+    }
+}
+
+impl NormalizedPitch {
+    /// A constructor for a NormalizedPitch, clamping `value` to `0..1`.
+    ///
+    /// NaN is treated as `0.0`.
+    pub fn new(value: f64, range: PitchRange) -> NormalizedPitch {
+        let value = if value.is_nan() {
+            0.0
+        } else {
+            value.clamp(0.0, 1.0)
+        };
+        NormalizedPitch { value, range }
+    }
+
+    /// The normalized value of the given pitch, clamped to the range.
+    pub fn from_step(step: Step, range: PitchRange) -> NormalizedPitch {
+        let (low, high) = (range.low().step(), range.high().step());
+        NormalizedPitch::new(f64::from((step.step() - low) / (high - low)), range)
+    }
+
+    /// The normalized value of the given frequency, clamped to the range.
+    pub fn from_hz(hz: Hz, range: PitchRange) -> NormalizedPitch {
+        NormalizedPitch::from_step(Step::from(hz), range)
+    }
+
+    /// Parse a pitch typed by the user within the given range, e.g. `"440"`, `"1.25 kHz"`, `"A4"`
+    /// or `"A4 +3¢"`, clamping it to the range.
+    ///
+    /// Accepts any positive frequency understood by the `FromStr` implementation of `Hz`.
+    pub fn parse(s: &str, range: PitchRange) -> Result<NormalizedPitch, ParsePitchError> {
+        match s.parse::<Hz>()? {
+            hz if hz.hz() > 0.0 => Ok(NormalizedPitch::from_hz(hz, range)),
+            _ => Err(ParsePitchError::invalid(s)),
+        }
+    }
+
+    /// The normalized value in `0..1`.
+    #[inline]
+    pub fn value(self) -> f64 {
+        self.value
+    }
+
+    /// The range of pitches to which the value is normalized.
+    #[inline]
+    pub fn range(self) -> PitchRange {
+        self.range
+    }
+
+    /// Convert to the unit value of a Step.
+    #[inline]
+    pub fn step(self) -> calc::Step {
+        let (low, high) = (self.range.low().step(), self.range.high().step());
+        low + (high - low) * self.value as calc::Step
+    }
+
+    /// Convert to a Step struct.
+    #[inline]
+    pub fn to_step(self) -> Step {
+        Step(self.step())
+    }
+
+    /// Convert to hz.
+    #[inline]
+    pub fn hz(self) -> calc::Hz {
+        self.to_step().hz()
+    }
+
+    /// Convert to a Hz struct.
+    #[inline]
+    pub fn to_hz(self) -> Hz {
+        Hz(self.hz())
+    }
+
+    /// The nearest pitch to a whole semitone that lies within the range.
+    pub fn snapped(self) -> NormalizedPitch {
+        let (low, high) = (self.range.low().step(), self.range.high().step());
+        let step = self.step().round().max(low.ceil()).min(high.floor());
+        NormalizedPitch::from_step(Step(step), self.range)
+    }
+}
+
+impl fmt::Display for NormalizedPitch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.range {
+            PitchRange::Frequency(_) => fmt::Display::fmt(&self.to_hz(), f),
+            PitchRange::Steps(..) => {
+                let step = self.step();
+                let note = LetterOctave::from_semitones(step.round() as calc::Semitones);
+                let cents = ((step - step.round()) * 100.0).round() as i32;
+                if cents == 0 {
+                    write!(f, "{}", note)
+                } else {
+                    write!(f, "{} {:+}¢", note, cents)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Letter;
+
+    fn piano() -> PitchRange {
+        PitchRange::steps(Step(21.0), Step(108.0)).unwrap()
+    }
+
+    #[test]
+    fn log_frequency_mapping() {
+        let range = PitchRange::Frequency(FrequencyRange::new(27.5, 3520.0).unwrap());
+        let a4 = NormalizedPitch::from_hz(Hz(440.0), range);
+        assert!((a4.value() - 4.0 / 7.0).abs() < 1e-6);
+        assert!((a4.hz() - 440.0).abs() < 1e-2);
+        assert_eq!(NormalizedPitch::new(2.0, range).value(), 1.0);
+        assert_eq!(NormalizedPitch::new(f64::NAN, range).value(), 0.0);
+        assert_eq!(
+            PitchRange::steps(Step(60.0), Step(60.0)),
+            Err(PitchError::InvalidStepRange(60.0, 60.0))
+        );
+        assert_eq!(
+            PitchRange::steps(Step(72.0), Step(60.0))
+                .unwrap_err()
+                .to_string(),
+            "invalid step range 72..60"
+        );
+    }
+
+    #[test]
+    fn snapping() {
+        let pitch = NormalizedPitch::from_step(Step(69.4), piano()).snapped();
+        assert!((pitch.step() - 69.0).abs() < 1e-4);
+        let range = PitchRange::steps(Step(60.5), Step(72.5)).unwrap();
+        let low = NormalizedPitch::new(0.0, range).snapped();
+        assert!((low.step() - 61.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn display() {
+        let range = PitchRange::Frequency(FrequencyRange::default());
+        let show = |hz| NormalizedPitch::from_hz(Hz(hz), range).to_string();
+        assert_eq!(show(440.0), "440 Hz");
+        assert_eq!(show(1250.0), "1.25 kHz");
+        assert_eq!(show(20_000.0), "20 kHz");
+        assert_eq!(show(27.5), "27.5 Hz");
+        let pitch = NormalizedPitch::from_hz(Hz(1250.0), range);
+        assert_eq!(format!("{:.3}", pitch), "1.250 kHz");
+
+        let note = |step| NormalizedPitch::from_step(Step(step), piano()).to_string();
+        assert_eq!(note(69.03), "A4 +3¢");
+        assert_eq!(note(61.0), "C#4");
+        assert_eq!(note(59.9), "C4 -10¢");
+    }
+
+    #[test]
+    fn parsing() {
+        let range = PitchRange::Frequency(FrequencyRange::default());
+        let parse = |s| NormalizedPitch::parse(s, piano()).unwrap().step();
+        assert!((parse("A4 +3¢") - 69.03).abs() < 1e-4);
+        assert!((parse("a4-12c") - 68.88).abs() < 1e-4);
+        assert!((parse("Bb3") - 58.0).abs() < 1e-4);
+        assert!((parse(" 440 Hz ") - 69.0).abs() < 1e-4);
+        assert!((parse("1.25 kHz") - Hz(1250.0).step()).abs() < 1e-4);
+        assert!((parse("1.25k") - Hz(1250.0).step()).abs() < 1e-4);
+        assert_eq!(
+            NormalizedPitch::parse("100k", piano()).unwrap().value(),
+            1.0
+        );
+        assert_eq!(
+            NormalizedPitch::parse("", range),
+            Err(ParsePitchError::Empty)
+        );
+        assert!(NormalizedPitch::parse("H4", range).is_err());
+        assert!(NormalizedPitch::parse("-5 Hz", range).is_err());
+
+        let pitch = NormalizedPitch::from_hz(Hz(1250.0), range);
+        let parsed = NormalizedPitch::parse(&pitch.to_string(), range).unwrap();
+        assert!((parsed.value() - pitch.value()).abs() < 1e-6);
+        assert_eq!("Cb4".parse(), Ok(LetterOctave(Letter::B, 3)));
+        assert_eq!("Db".parse(), Ok(Letter::Db));
+    }
+}
@@ -1,16 +1,22 @@
 use super::{
-    calc, hz_from_perc, letter_octave_from_perc, mel_from_perc, scaled_perc_from_perc,
-    step_from_perc, Hz, Letter, LetterOctave, Mel, Octave, ScaleWeight, ScaledPerc, Step,
-    DEFAULT_SCALE_WEIGHT,
+    bark_from_hz, calc, erb_from_hz, hz_from_perc, hz_from_perc_in_range, letter_octave_from_perc,
+    mel_from_perc, scaled_perc_from_perc, scaled_perc_from_perc_with_curve, step_from_perc, Bark,
+    Curve, Erb, FrequencyRange, Hz, Letter, LetterOctave, Mel, Octave, Pitch, ScaleWeight,
+    ScaledPerc, Step, DEFAULT_SCALE_WEIGHT,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
 
 /// Pitch representation in the form of a percentage between the min and max hz.
+///
+/// Ordered and hashed by the percentage with `f64::total_cmp`, so NaN sorts last and equals
+/// itself.
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde::PercRepr"))]
 pub struct Perc(pub calc::Perc);
 
 impl Perc {
@@ -29,9 +35,22 @@ impl Perc {
     }
 
     /// Convert to the equivalent frequency in Hz.
+    #[deprecated(note = "use Pitch::to_hz")]
     #[inline]
     pub fn to_hz(self) -> Hz {
-        Hz(self.hz())
+        Pitch::to_hz(self)
+    }
+
+    /// Convert to hz, interpreting the value as a percentage of the given frequency range.
+    #[inline]
+    pub fn hz_in_range(self, range: FrequencyRange) -> calc::Hz {
+        hz_from_perc_in_range(self.perc(), range)
+    }
+
+    /// Convert to a Hz struct, interpreting the value as a percentage of the given frequency range.
+    #[inline]
+    pub fn to_hz_in_range(self, range: FrequencyRange) -> Hz {
+        Hz(self.hz_in_range(range))
     }
 
     /// Convert to a (Letter, Octave).
@@ -55,10 +74,10 @@ impl Perc {
     }
 
     /// Convert to LetterOctave.
+    #[deprecated(note = "use Pitch::to_letter_octave")]
     #[inline]
     pub fn to_letter_octave(self) -> LetterOctave {
-        let (letter, octave) = self.letter_octave();
-        LetterOctave(letter, octave)
+        Pitch::to_letter_octave(self)
     }
 
     /// Convert to the unit value of a Mel.
@@ -68,9 +87,36 @@ impl Perc {
     }
 
     /// Convert to a Mel struct.
+    #[deprecated(note = "use Pitch::to_mel")]
     #[inline]
     pub fn to_mel(self) -> Mel {
-        Mel(self.mel())
+        Pitch::to_mel(self)
+    }
+
+    /// Convert to the unit value of a Bark.
+    #[inline]
+    pub fn bark(self) -> calc::Bark {
+        bark_from_hz(self.hz())
+    }
+
+    /// Convert to a Bark struct.
+    #[deprecated(note = "use Pitch::to_bark")]
+    #[inline]
+    pub fn to_bark(self) -> Bark {
+        Pitch::to_bark(self)
+    }
+
+    /// Convert to the unit value of an Erb.
+    #[inline]
+    pub fn erb(self) -> calc::Erb {
+        erb_from_hz(self.hz())
+    }
+
+    /// Convert to an Erb struct.
+    #[deprecated(note = "use Pitch::to_erb")]
+    #[inline]
+    pub fn to_erb(self) -> Erb {
+        Pitch::to_erb(self)
     }
 
     /// Convert to a scaled percentage of the human hearing range with a given weight.
@@ -92,9 +138,22 @@ impl Perc {
     }
 
     /// Convert to a scaled percentage of the human hearing range.
+    #[deprecated(note = "use Pitch::to_scaled_perc")]
     #[inline]
     pub fn to_scaled_perc(self) -> ScaledPerc {
-        self.to_scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+        Pitch::to_scaled_perc(self)
+    }
+
+    /// Convert to a percentage scaled along the given curve.
+    #[inline]
+    pub fn scaled_perc_with_curve<C: Curve>(self, curve: &C) -> calc::Perc {
+        scaled_perc_from_perc_with_curve(self.perc(), curve)
+    }
+
+    /// Convert to a ScaledPerc struct scaled along the given curve.
+    #[inline]
+    pub fn to_scaled_perc_with_curve<C: Curve>(self, curve: C) -> ScaledPerc<C> {
+        ScaledPerc(self.scaled_perc_with_curve(&curve), curve)
     }
 
     /// Convert to the unit value of a Step.
@@ -104,9 +163,10 @@ impl Perc {
     }
 
     /// Convert to a floating point MIDI-esque Step.
+    #[deprecated(note = "use Pitch::to_step")]
     #[inline]
     pub fn to_step(self) -> Step {
-        Step(self.step())
+        Pitch::to_step(self)
     }
 }
 
@@ -126,30 +186,6 @@ impl Sub for Perc {
     }
 }
 
-impl Mul for Perc {
-    type Output = Perc;
-    #[inline]
-    fn mul(self, rhs: Perc) -> Perc {
-        Perc(self.perc() * rhs.perc())
-    }
-}
-
-impl Div for Perc {
-    type Output = Perc;
-    #[inline]
-    fn div(self, rhs: Perc) -> Perc {
-        Perc(self.perc() / rhs.perc())
-    }
-}
-
-impl Rem for Perc {
-    type Output = Perc;
-    #[inline]
-    fn rem(self, rhs: Perc) -> Perc {
-        Perc(self.perc() % rhs.perc())
-    }
-}
-
 impl Neg for Perc {
     type Output = Perc;
     #[inline]
@@ -161,7 +197,7 @@ impl Neg for Perc {
 impl PartialEq for Perc {
     #[inline]
     fn eq(&self, other: &Perc) -> bool {
-        self.perc() == other.perc()
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -170,6 +206,20 @@ impl Eq for Perc {}
 impl PartialOrd for Perc {
     #[inline]
     fn partial_cmp(&self, other: &Perc) -> Option<Ordering> {
-        self.perc().partial_cmp(&other.perc())
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Perc {
+    #[inline]
+    fn cmp(&self, other: &Perc) -> Ordering {
+        self.perc().total_cmp(&other.perc())
+    }
+}
+
+impl Hash for Perc {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.perc().to_bits().hash(state);
     }
 }
@@ -0,0 +1,322 @@
+//! A common interface over every pitch representation.
+//!
+//! Each representation implements `Pitch` by converting to and from `Hz`, and receives conversions
+//! to every other representation for free. The crate's own representations override these with
+//! direct conversions. Their inherent methods of the same names, e.g. `Step::to_hz`, are
+//! deprecated and will be removed, after which the trait must be in scope to call `step.to_hz()`.
+//! `AnyPitch` holds a pitch in any representation, e.g. as read from a configuration file.
+
+use crate::{Bark, DefaultCurve, Erb, Hz, LetterOctave, Mel, MelFormula, Perc, ScaledPerc, Step};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A representation of pitch that may be converted to and from a frequency.
+///
+/// Only `to_hz` and `from_hz` are required. The remaining conversions go via `Hz` by default, and
+/// may be overridden where a more direct conversion is available.
+pub trait Pitch: Copy {
+    /// Convert to a frequency.
+    fn to_hz(self) -> Hz;
+
+    /// Convert from a frequency.
+    fn from_hz(hz: Hz) -> Self;
+
+    /// Convert to any other representation.
+    #[inline]
+    fn to_pitch<P: Pitch>(self) -> P {
+        P::from_hz(self.to_hz())
+    }
+
+    /// Convert to a Step struct.
+    #[inline]
+    fn to_step(self) -> Step {
+        Step::from_hz(self.to_hz())
+    }
+
+    /// Convert to a Mel struct.
+    #[inline]
+    fn to_mel(self) -> Mel {
+        Mel::from_hz(self.to_hz())
+    }
+
+    /// Convert to a Bark struct.
+    #[inline]
+    fn to_bark(self) -> Bark {
+        Bark::from_hz(self.to_hz())
+    }
+
+    /// Convert to an Erb struct.
+    #[inline]
+    fn to_erb(self) -> Erb {
+        Erb::from_hz(self.to_hz())
+    }
+
+    /// Convert to a Perc struct.
+    #[inline]
+    fn to_perc(self) -> Perc {
+        Perc::from_hz(self.to_hz())
+    }
+
+    /// Convert to a ScaledPerc struct with the default weight.
+    #[inline]
+    fn to_scaled_perc(self) -> ScaledPerc {
+        ScaledPerc::from_hz(self.to_hz())
+    }
+
+    /// Convert to the LetterOctave with the closest pitch.
+    #[inline]
+    fn to_letter_octave(self) -> LetterOctave {
+        Pitch::to_letter_octave(self.to_step())
+    }
+}
+
+/// A pitch in any of the crate's representations.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AnyPitch {
+    Hz(Hz),
+    Mel(Mel),
+    Bark(Bark),
+    Erb(Erb),
+    Step(Step),
+    Perc(Perc),
+    ScaledPerc(ScaledPerc),
+    LetterOctave(LetterOctave),
+}
+
+/// Implement `Pitch` for representations with an inherent unit value for every other
+/// representation, converting directly rather than via `Hz`.
+macro_rules! impl_pitch {
+    ($($T:ident => $from:ident),*) => {
+        $(
+            impl Pitch for $T {
+                #[inline]
+                fn to_hz(self) -> Hz {
+                    Hz(self.hz())
+                }
+
+                #[inline]
+                fn from_hz(hz: Hz) -> $T {
+                    Pitch::$from(hz)
+                }
+
+                #[inline]
+                fn to_step(self) -> Step {
+                    Step(self.step())
+                }
+
+                #[inline]
+                fn to_mel(self) -> Mel {
+                    Mel::new(self.mel())
+                }
+
+                #[inline]
+                fn to_bark(self) -> Bark {
+                    Bark(self.bark())
+                }
+
+                #[inline]
+                fn to_erb(self) -> Erb {
+                    Erb(self.erb())
+                }
+
+                #[inline]
+                fn to_perc(self) -> Perc {
+                    Perc(self.perc())
+                }
+
+                #[inline]
+                fn to_scaled_perc(self) -> ScaledPerc {
+                    ScaledPerc::new(self.scaled_perc())
+                }
+
+                #[inline]
+                fn to_letter_octave(self) -> LetterOctave {
+                    let (letter, octave) = self.letter_octave();
+                    LetterOctave(letter, octave)
+                }
+            }
+        )*
+    };
+}
+
+impl_pitch!(
+    Hz => to_hz,
+    Step => to_step,
+    LetterOctave => to_letter_octave,
+    Perc => to_perc
+);
+
+impl Pitch for Bark {
+    #[inline]
+    fn to_hz(self) -> Hz {
+        Hz(self.hz())
+    }
+
+    #[inline]
+    fn from_hz(hz: Hz) -> Bark {
+        Bark(hz.bark())
+    }
+
+    #[inline]
+    fn to_bark(self) -> Bark {
+        self
+    }
+}
+
+impl Pitch for Erb {
+    #[inline]
+    fn to_hz(self) -> Hz {
+        Hz(self.hz())
+    }
+
+    #[inline]
+    fn from_hz(hz: Hz) -> Erb {
+        Erb(hz.erb())
+    }
+
+    #[inline]
+    fn to_erb(self) -> Erb {
+        self
+    }
+}
+
+impl<S: MelFormula> Pitch for Mel<S> {
+    #[inline]
+    fn to_hz(self) -> Hz {
+        Hz(self.hz())
+    }
+
+    #[inline]
+    fn from_hz(hz: Hz) -> Mel<S> {
+        hz.to_mel_with_scale(S::default())
+    }
+
+    #[inline]
+    fn to_mel(self) -> Mel {
+        self.to_scale()
+    }
+}
+
+/// Percentages created from another pitch are scaled along the curve's `DefaultCurve`.
+impl<C: DefaultCurve + Copy> Pitch for ScaledPerc<C> {
+    #[inline]
+    fn to_hz(self) -> Hz {
+        Hz(self.hz())
+    }
+
+    #[inline]
+    fn from_hz(hz: Hz) -> ScaledPerc<C> {
+        hz.to_scaled_perc_with_curve(C::default_curve())
+    }
+
+    #[inline]
+    fn to_perc(self) -> Perc {
+        Perc(self.perc())
+    }
+
+    #[inline]
+    fn to_scaled_perc(self) -> ScaledPerc {
+        Pitch::to_scaled_perc(self.to_perc())
+    }
+}
+
+impl Pitch for AnyPitch {
+    fn to_hz(self) -> Hz {
+        match self {
+            AnyPitch::Hz(hz) => hz,
+            AnyPitch::Mel(mel) => Pitch::to_hz(mel),
+            AnyPitch::Bark(bark) => bark.to_hz(),
+            AnyPitch::Erb(erb) => erb.to_hz(),
+            AnyPitch::Step(step) => Pitch::to_hz(step),
+            AnyPitch::Perc(perc) => Pitch::to_hz(perc),
+            AnyPitch::ScaledPerc(scaled) => scaled.to_hz(),
+            AnyPitch::LetterOctave(letter_octave) => Pitch::to_hz(letter_octave),
+        }
+    }
+
+    #[inline]
+    fn from_hz(hz: Hz) -> AnyPitch {
+        AnyPitch::Hz(hz)
+    }
+}
+
+macro_rules! impl_from_for_any_pitch {
+    ($($T:ident),*) => {
+        $(
+            impl From<$T> for AnyPitch {
+                fn from(pitch: $T) -> AnyPitch {
+                    AnyPitch::$T(pitch)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_any_pitch!(Hz, Mel, Bark, Erb, Step, Perc, ScaledPerc, LetterOctave);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::Logarithmic;
+    use crate::mel::Slaney;
+    use crate::{Letter, Ratio};
+
+    fn octave_above<P: Pitch>(pitch: P) -> P {
+        P::from_hz(pitch.to_hz() * Ratio::OCTAVE)
+    }
+
+    #[test]
+    fn generic_conversions() {
+        assert_eq!(octave_above(Hz(220.0)), Hz(440.0));
+        assert!((octave_above(Step(57.0)).step() - 69.0).abs() < 1e-4);
+        let a4 = LetterOctave(Letter::A, 4);
+        assert_eq!(octave_above(a4), LetterOctave(Letter::A, 5));
+        assert_eq!(Pitch::to_step(a4), Step(69.0));
+        let mel: Mel = a4.to_pitch();
+        assert!((mel.to_pitch::<Hz>().hz() - 440.0).abs() < 1e-2);
+
+        let knob = ScaledPerc::<Logarithmic>::from_hz(Hz(440.0));
+        assert_eq!(*knob.curve(), Logarithmic::default());
+        assert_eq!(knob.to_letter_octave(), a4);
+        assert_eq!(
+            octave_above(knob).to_letter_octave(),
+            LetterOctave(Letter::A, 5)
+        );
+        assert_eq!(knob.to_scaled_perc(), ScaledPerc::from(a4));
+        let slaney: Mel<Slaney> = knob.to_pitch();
+        assert!((Hz::from(slaney.to_mel()).hz() - 440.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn any_pitch() {
+        let pitches: Vec<AnyPitch> = vec![
+            Hz(440.0).into(),
+            Step(69.0).into(),
+            LetterOctave(Letter::A, 4).into(),
+            Mel::from(Hz(440.0)).into(),
+            ScaledPerc::from(Hz(440.0)).into(),
+        ];
+        for pitch in pitches {
+            assert!((pitch.to_hz().hz() - 440.0).abs() < 1e-2, "{:?}", pitch);
+            assert_eq!(pitch.to_letter_octave(), LetterOctave(Letter::A, 4));
+        }
+        assert_eq!(AnyPitch::from_hz(Hz(440.0)), AnyPitch::Hz(Hz(440.0)));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_inherent_conversions() {
+        let a4 = LetterOctave(Letter::A, 4);
+        assert_eq!(a4.to_step(), Pitch::to_step(a4));
+        assert_eq!(a4.to_hz(), Pitch::to_hz(a4));
+        assert_eq!(Hz(440.0).to_letter_octave(), a4);
+        assert_eq!(Step(69.0).to_mel(), Pitch::to_mel(Step(69.0)));
+        assert_eq!(Perc(0.5).to_scaled_perc(), Pitch::to_scaled_perc(Perc(0.5)));
+        assert_eq!(Mel::new(549.64).to_letter_octave(), a4);
+        assert_eq!(
+            ScaledPerc::new(0.5).to_hz(),
+            Pitch::to_hz(ScaledPerc::new(0.5))
+        );
+    }
+}
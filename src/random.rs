@@ -0,0 +1,496 @@
+//! Distributions and melodic generators for sampling random pitches.
+//!
+//! The distributions implement `rand`'s `Distribution` for every `Pitch`, sampling in the space
+//! natural to each, e.g. uniformly in steps for `LogUniformHz`, and converting the result. Samples
+//! taken as a `LetterOctave` are rounded to the nearest note.
+//!
+//! `RandomWalk` and `MarkovMelody` generate melodies note by note, each note depending on the
+//! last. All randomness comes from the given `Rng`, so a seeded generator reproduces a melody.
+
+use crate::{FrequencyRange, Hz, Interval, Letter, LetterOctave, Pitch, PitchError, Step};
+use rand::distr::Distribution;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Notes sampled uniformly from an inclusive range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UniformNote {
+    low: LetterOctave,
+    high: LetterOctave,
+}
+
+/// Frequencies sampled uniformly in hz, favouring the upper octaves of the range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UniformHz {
+    pub range: FrequencyRange,
+}
+
+/// Frequencies sampled uniformly in log-frequency, so that every octave of the range is equally
+/// likely.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LogUniformHz {
+    pub range: FrequencyRange,
+}
+
+/// Pitches normally distributed around a centre, with a standard deviation in cents.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NormalCents {
+    /// The mean pitch.
+    pub centre: Step,
+    /// The standard deviation in cents.
+    pub deviation: f32,
+}
+
+/// Notes sampled with probabilities proportional to their weights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedNotes {
+    notes: Vec<LetterOctave>,
+    /// The running total of the weights of `notes`.
+    cumulative: Vec<f32>,
+}
+
+/// A melody that moves from each note to one chosen uniformly among those reachable within the
+/// step constraints.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RandomWalk {
+    /// The lowest and highest notes that the walk may reach.
+    pub range: RangeInclusive<LetterOctave>,
+    /// The smallest interval by which the walk may move, e.g. `Interval::UNISON` to allow repeated
+    /// notes.
+    pub min_step: Interval,
+    /// The largest interval by which the walk may move.
+    pub max_step: Interval,
+    /// The letters on which the walk may land, or every letter if empty.
+    pub scale: Vec<Letter>,
+    current: LetterOctave,
+}
+
+/// A first-order Markov chain over the intervals between successive notes, trained on example
+/// melodies.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkovMelody {
+    /// The number of times each interval followed each interval, or began a melody (`None`).
+    transitions: BTreeMap<Option<Interval>, BTreeMap<Interval, u32>>,
+}
+
+impl UniformNote {
+    /// Notes from the lowest to the highest note of the range inclusive.
+    ///
+    /// Returns an error if the range is empty.
+    pub fn new(range: RangeInclusive<LetterOctave>) -> Result<UniformNote, PitchError> {
+        let (low, high) = range.into_inner();
+        if low <= high {
+            Ok(UniformNote { low, high })
+        } else {
+            Err(PitchError::InvalidNoteRange(low, high))
+        }
+    }
+}
+
+impl<P: Pitch> Distribution<P> for UniformNote {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> P {
+        let semitones = rng.random_range(self.low.semitones()..=self.high.semitones());
+        LetterOctave::from_semitones(semitones).to_pitch()
+    }
+}
+
+impl UniformHz {
+    /// A constructor for a UniformHz.
+    pub fn new(range: FrequencyRange) -> UniformHz {
+        UniformHz { range }
+    }
+}
+
+impl<P: Pitch> Distribution<P> for UniformHz {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> P {
+        Hz(self.range.min() + self.range.span() * rng.random::<f32>()).to_pitch()
+    }
+}
+
+impl LogUniformHz {
+    /// A constructor for a LogUniformHz.
+    pub fn new(range: FrequencyRange) -> LogUniformHz {
+        LogUniformHz { range }
+    }
+}
+
+impl<P: Pitch> Distribution<P> for LogUniformHz {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> P {
+        let low = Hz(self.range.min()).step();
+        let high = Hz(self.range.max()).step();
+        Step(low + (high - low) * rng.random::<f32>()).to_pitch()
+    }
+}
+
+impl NormalCents {
+    /// Pitches around `centre` with a standard deviation of `deviation` cents.
+    pub fn new<P: Pitch>(centre: P, deviation: f32) -> NormalCents {
+        NormalCents {
+            centre: centre.to_step(),
+            deviation,
+        }
+    }
+}
+
+impl<P: Pitch> Distribution<P> for NormalCents {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> P {
+        let cents = self.deviation * standard_normal(rng);
+        Step(self.centre.step() + cents / 100.0).to_pitch()
+    }
+}
+
+/// A sample from the standard normal distribution, using the Box-Muller transform.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f32 {
+    // `1 - x` lies in `(0, 1]`, avoiding the logarithm of zero.
+    let u = 1.0 - rng.random::<f32>();
+    let v = rng.random::<f32>();
+    (-2.0 * u.ln()).sqrt() * (std::f32::consts::TAU * v).cos()
+}
+
+impl WeightedNotes {
+    /// Each note with its weight. Notes with a weight of zero are never sampled.
+    ///
+    /// Returns an error if any weight is negative or not finite, or if all are zero.
+    pub fn new<I>(weights: I) -> Result<WeightedNotes, PitchError>
+    where
+        I: IntoIterator<Item = (LetterOctave, f32)>,
+    {
+        let mut notes = Vec::new();
+        let mut cumulative = Vec::new();
+        let mut total = 0.0;
+        for (note, weight) in weights {
+            if !(weight.is_finite() && weight >= 0.0) {
+                return Err(PitchError::InvalidDistribution);
+            }
+            if weight > 0.0 {
+                total += weight;
+                notes.push(note);
+                cumulative.push(total);
+            }
+        }
+        if notes.is_empty() || !total.is_finite() {
+            return Err(PitchError::InvalidDistribution);
+        }
+        Ok(WeightedNotes { notes, cumulative })
+    }
+
+    /// Every note in the range that is a degree of the scale on `tonic`, weighted by its degree.
+    ///
+    /// `degrees` pairs the interval of each degree above the tonic with its weight, e.g.
+    /// `(Interval::UNISON, 3.0)` and `(Interval::PERFECT_FIFTH, 2.0)` favour the tonic and fifth.
+    pub fn scale(
+        tonic: Letter,
+        degrees: &[(Interval, f32)],
+        range: RangeInclusive<LetterOctave>,
+    ) -> Result<WeightedNotes, PitchError> {
+        if degrees.iter().any(|&(_, w)| !(w.is_finite() && w >= 0.0)) {
+            return Err(PitchError::InvalidDistribution);
+        }
+        let (low, high) = range.into_inner();
+        let notes = (low.semitones()..=high.semitones()).map(LetterOctave::from_semitones);
+        WeightedNotes::new(notes.filter_map(|note| {
+            degrees
+                .iter()
+                .find(|&&(interval, _)| tonic + interval == note.letter())
+                .map(|&(_, weight)| (note, weight))
+        }))
+    }
+
+    /// The notes that may be sampled, i.e. those given with a positive weight.
+    #[inline]
+    pub fn notes(&self) -> &[LetterOctave] {
+        &self.notes
+    }
+}
+
+impl<P: Pitch> Distribution<P> for WeightedNotes {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> P {
+        let total = self.cumulative[self.cumulative.len() - 1];
+        let x = total * rng.random::<f32>();
+        let index = self.cumulative.partition_point(|&c| c <= x);
+        self.notes[index.min(self.notes.len() - 1)].to_pitch()
+    }
+}
+
+impl RandomWalk {
+    /// A walk from `start` within `range`, moving by one or two semitones at a time.
+    ///
+    /// Returns an error if the range is empty or does not contain `start`.
+    pub fn new(
+        start: LetterOctave,
+        range: RangeInclusive<LetterOctave>,
+    ) -> Result<RandomWalk, PitchError> {
+        if range.is_empty() {
+            let (low, high) = range.into_inner();
+            return Err(PitchError::InvalidNoteRange(low, high));
+        }
+        if !range.contains(&start) {
+            return Err(PitchError::NoteOutOfRange(start));
+        }
+        Ok(RandomWalk {
+            range,
+            min_step: Interval::MINOR_SECOND,
+            max_step: Interval::MAJOR_SECOND,
+            scale: Vec::new(),
+            current: start,
+        })
+    }
+
+    /// The note on which the walk currently rests.
+    #[inline]
+    pub fn current(&self) -> LetterOctave {
+        self.current
+    }
+
+    /// Move to the next note and return it.
+    ///
+    /// The walk stays on the current note if no other note satisfies the constraints.
+    pub fn next_note<R: Rng + ?Sized>(&mut self, rng: &mut R) -> LetterOctave {
+        let min = self.min_step.semitones().abs();
+        let max = self.max_step.semitones().abs();
+        let candidates: Vec<LetterOctave> = (-max..=max)
+            .filter(|step| step.abs() >= min)
+            .map(|step| self.current + Interval(step))
+            .filter(|note| self.range.contains(note))
+            .filter(|note| self.scale.is_empty() || self.scale.contains(&note.letter()))
+            .collect();
+        if !candidates.is_empty() {
+            self.current = candidates[rng.random_range(0..candidates.len())];
+        }
+        self.current
+    }
+
+    /// A melody of `len` notes, beginning with the current note.
+    pub fn melody<R: Rng + ?Sized>(&mut self, rng: &mut R, len: usize) -> Vec<LetterOctave> {
+        let mut melody = Vec::with_capacity(len);
+        if len > 0 {
+            melody.push(self.current);
+        }
+        for _ in 1..len {
+            melody.push(self.next_note(rng));
+        }
+        melody
+    }
+}
+
+impl MarkovMelody {
+    /// A chain trained on each of the given melodies.
+    pub fn from_melodies<'a, I>(melodies: I) -> MarkovMelody
+    where
+        I: IntoIterator<Item = &'a [LetterOctave]>,
+    {
+        let mut chain = MarkovMelody::default();
+        for melody in melodies {
+            chain.train(melody);
+        }
+        chain
+    }
+
+    /// Count the transitions between the successive intervals of the melody.
+    pub fn train(&mut self, melody: &[LetterOctave]) {
+        let mut previous = None;
+        for pair in melody.windows(2) {
+            let interval = pair[1] - pair[0];
+            let counts = self.transitions.entry(previous).or_default();
+            *counts.entry(interval).or_insert(0) += 1;
+            previous = Some(interval);
+        }
+    }
+
+    /// A melody of `len` notes within `range`, beginning with `start`.
+    ///
+    /// Each interval is chosen among those that followed the previous interval in training, in
+    /// proportion to how often they did. Where none of those remain within the range, any trained
+    /// interval that does may be chosen, and failing that the note is repeated.
+    pub fn generate<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        start: LetterOctave,
+        len: usize,
+        range: RangeInclusive<LetterOctave>,
+    ) -> Vec<LetterOctave> {
+        let mut melody = Vec::with_capacity(len);
+        let mut note = start;
+        let mut previous = None;
+        for i in 0..len {
+            if i > 0 {
+                let interval = self.next_interval(rng, previous, note, &range);
+                note = note + interval;
+                previous = Some(interval);
+            }
+            melody.push(note);
+        }
+        melody
+    }
+
+    /// Choose the interval to follow `previous` from `note`.
+    fn next_interval<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        previous: Option<Interval>,
+        note: LetterOctave,
+        range: &RangeInclusive<LetterOctave>,
+    ) -> Interval {
+        let within = |&(interval, _): &(Interval, u32)| range.contains(&(note + interval));
+        let mut candidates: Vec<(Interval, u32)> = self
+            .transitions
+            .get(&previous)
+            .into_iter()
+            .flatten()
+            .map(|(&interval, &count)| (interval, count))
+            .filter(within)
+            .collect();
+        if candidates.is_empty() {
+            let mut counts = BTreeMap::new();
+            for (&interval, &count) in self.transitions.values().flatten() {
+                *counts.entry(interval).or_insert(0) += count;
+            }
+            candidates = counts.into_iter().filter(within).collect();
+        }
+        let total: u32 = candidates.iter().map(|&(_, count)| count).sum();
+        if total == 0 {
+            return Interval::UNISON;
+        }
+        let mut x = rng.random_range(0..total);
+        for (interval, count) in candidates {
+            if x < count {
+                return interval;
+            }
+            x -= count;
+        }
+        Interval::UNISON
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    /// A xorshift generator, as `rand`'s seedable generators are not enabled.
+    struct TestRng(u64);
+
+    impl RngCore for TestRng {
+        fn next_u32(&mut self) -> u32 {
+            (self.next_u64() >> 32) as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            rand::rand_core::impls::fill_bytes_via_next(self, dest)
+        }
+    }
+
+    fn note(letter: Letter, octave: i32) -> LetterOctave {
+        LetterOctave(letter, octave)
+    }
+
+    #[test]
+    fn uniform_distributions() {
+        let rng = &mut TestRng(0x2545_f491_4f6c_dd1d);
+        let notes = UniformNote::new(note(Letter::C, 4)..=note(Letter::B, 4)).unwrap();
+        let samples: Vec<LetterOctave> = (0..1200).map(|_| notes.sample(rng)).collect();
+        assert!(samples.iter().all(|n| n.octave() == 4));
+        assert!(samples.contains(&note(Letter::C, 4)) && samples.contains(&note(Letter::B, 4)));
+        let error = UniformNote::new(note(Letter::D, 4)..=note(Letter::C, 4)).unwrap_err();
+        assert_eq!(
+            error,
+            PitchError::InvalidNoteRange(note(Letter::D, 4), note(Letter::C, 4))
+        );
+        assert_eq!(error.to_string(), "invalid note range D4..C4");
+
+        let range = FrequencyRange::new(55.0, 880.0).unwrap();
+        let steps: Vec<Step> = (0..4000)
+            .map(|_| LogUniformHz::new(range).sample(rng))
+            .collect();
+        let below_a2 = steps.iter().filter(|s| s.step() < 45.0).count();
+        // 55hz..880hz spans four octaves, one of which lies below A2.
+        assert!((below_a2 as f32 / 4000.0 - 0.25).abs() < 0.03);
+        let hz: Vec<Hz> = (0..4000)
+            .map(|_| UniformHz::new(range).sample(rng))
+            .collect();
+        assert!(hz.iter().all(|&hz| range.contains(hz)));
+        let below_a2 = hz.iter().filter(|hz| hz.hz() < 110.0).count();
+        assert!((below_a2 as f32 / 4000.0 - 55.0 / 825.0).abs() < 0.03);
+    }
+
+    #[test]
+    fn normal_cents() {
+        let rng = &mut TestRng(0x9e37_79b9_7f4a_7c15);
+        let distribution = NormalCents::new(Hz(440.0), 50.0);
+        let cents: Vec<f32> = (0..10_000)
+            .map(|_| (Distribution::<Step>::sample(&distribution, rng).step() - 69.0) * 100.0)
+            .collect();
+        let mean = cents.iter().sum::<f32>() / cents.len() as f32;
+        let variance = cents.iter().map(|c| (c - mean).powi(2)).sum::<f32>() / cents.len() as f32;
+        assert!(mean.abs() < 2.0, "{}", mean);
+        assert!((variance.sqrt() - 50.0).abs() < 2.0, "{}", variance.sqrt());
+    }
+
+    #[test]
+    fn weighted_scale() {
+        let rng = &mut TestRng(0xdead_beef_cafe_f00d);
+        let degrees = [(Interval::UNISON, 3.0), (Interval::PERFECT_FIFTH, 1.0)];
+        let range = note(Letter::C, 4)..=note(Letter::C, 5);
+        let distribution = WeightedNotes::scale(Letter::C, &degrees, range).unwrap();
+        assert_eq!(
+            distribution.notes(),
+            &[note(Letter::C, 4), note(Letter::G, 4), note(Letter::C, 5)]
+        );
+        let samples: Vec<LetterOctave> = (0..7000).map(|_| distribution.sample(rng)).collect();
+        let g = samples.iter().filter(|n| n.letter() == Letter::G).count();
+        assert!((g as f32 / 7000.0 - 1.0 / 7.0).abs() < 0.02);
+        assert_eq!(
+            WeightedNotes::new(vec![(note(Letter::A, 4), -1.0)]),
+            Err(PitchError::InvalidDistribution)
+        );
+        assert!(WeightedNotes::new(vec![(note(Letter::A, 4), 0.0)]).is_err());
+    }
+
+    #[test]
+    fn melodic_generators() {
+        let rng = &mut TestRng(0x0123_4567_89ab_cdef);
+        let range = note(Letter::C, 4)..=note(Letter::C, 5);
+        let mut walk = RandomWalk::new(note(Letter::E, 4), range.clone()).unwrap();
+        walk.scale = vec![Letter::C, Letter::D, Letter::E, Letter::G, Letter::A];
+        walk.max_step = Interval::MINOR_THIRD;
+        let melody = walk.melody(rng, 64);
+        assert_eq!(melody.len(), 64);
+        assert_eq!(melody[0], note(Letter::E, 4));
+        assert_eq!(walk.current(), melody[63]);
+        for pair in melody.windows(2) {
+            let step = (pair[1] - pair[0]).semitones().abs();
+            assert!((1..=3).contains(&step), "{:?}", pair);
+            assert!(range.contains(&pair[1]) && walk.scale.contains(&pair[1].letter()));
+        }
+
+        let scale = [0, 2, 4, 5, 7, 5, 4, 2, 0].map(|s| note(Letter::C, 4) + Interval(s));
+        let chain = MarkovMelody::from_melodies([&scale[..]]);
+        let melody = chain.generate(rng, note(Letter::C, 4), 32, range.clone());
+        assert_eq!(melody[0], note(Letter::C, 4));
+        for pair in melody.windows(2) {
+            let step = (pair[1] - pair[0]).semitones().abs();
+            assert!((1..=3).contains(&step), "{:?}", pair);
+            assert!(range.contains(&pair[1]));
+        }
+        let empty = MarkovMelody::default().generate(rng, note(Letter::A, 4), 3, range.clone());
+        assert_eq!(empty, vec![note(Letter::A, 4); 3]);
+
+        assert_eq!(
+            RandomWalk::new(note(Letter::C, 9), range).unwrap_err(),
+            PitchError::NoteOutOfRange(note(Letter::C, 9))
+        );
+        assert_eq!(
+            RandomWalk::new(note(Letter::C, 4), note(Letter::C, 5)..=note(Letter::C, 4))
+                .unwrap_err(),
+            PitchError::InvalidNoteRange(note(Letter::C, 5), note(Letter::C, 4))
+        );
+    }
+}
@@ -1,20 +1,33 @@
 use super::{
-    calc, hz_from_scaled_perc, letter_octave_from_scaled_perc, mel_from_scaled_perc,
-    perc_from_scaled_perc, step_from_scaled_perc, Hz, Letter, LetterOctave, Mel, Octave, Perc,
-    Step,
+    bark_from_hz, calc, erb_from_hz, hz_from_perc, hz_from_perc_in_range, letter_octave_from_perc,
+    mel_from_perc, perc_from_scaled_perc_with_curve, step_from_perc, Bark, Curve, Erb,
+    FrequencyRange, Hz, Letter, LetterOctave, Mel, Octave, Perc, Pitch, Step,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
 
 pub type ScaleWeight = calc::Weight;
 pub const DEFAULT_SCALE_WEIGHT: ScaleWeight = 4.0;
 
 /// Pitch representation in the form of a scaled percentage between the min and max hz.
+///
+/// The percentage is scaled along a `Curve`, by default the power curve given by a `ScaleWeight`.
+///
+/// Compared and hashed by the equivalent unscaled `Perc`, so values with different weights
+/// representing the same pitch are equal.
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct ScaledPerc(pub calc::Perc, pub ScaleWeight);
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "crate::serde::ScaledPercRepr<C>",
+        bound(deserialize = "C: Deserialize<'de> + Curve")
+    )
+)]
+pub struct ScaledPerc<C = ScaleWeight>(pub calc::Perc, pub C);
 
 impl ScaledPerc {
     /// A constructor for a ScaledPerc that uses the default weight.
@@ -22,38 +35,99 @@ impl ScaledPerc {
         ScaledPerc(perc, DEFAULT_SCALE_WEIGHT)
     }
 
+    /// Return the scale weight.
+    #[inline]
+    pub fn scale_weight(&self) -> ScaleWeight {
+        let ScaledPerc(_, weight) = *self;
+        weight
+    }
+
+    /// Convert to the equivalent frequency in Hz.
+    #[deprecated(note = "use Pitch::to_hz")]
+    #[inline]
+    pub fn to_hz(&self) -> Hz {
+        Pitch::to_hz(*self)
+    }
+
+    /// Convert to LetterOctave.
+    #[deprecated(note = "use Pitch::to_letter_octave")]
+    #[inline]
+    pub fn to_letter_octave(&self) -> LetterOctave {
+        Pitch::to_letter_octave(*self)
+    }
+
+    /// Convert to a Mel struct.
+    #[deprecated(note = "use Pitch::to_mel")]
+    #[inline]
+    pub fn to_mel(&self) -> Mel {
+        Pitch::to_mel(*self)
+    }
+
+    /// Convert to a Bark struct.
+    #[deprecated(note = "use Pitch::to_bark")]
+    #[inline]
+    pub fn to_bark(&self) -> Bark {
+        Pitch::to_bark(*self)
+    }
+
+    /// Convert to an Erb struct.
+    #[deprecated(note = "use Pitch::to_erb")]
+    #[inline]
+    pub fn to_erb(&self) -> Erb {
+        Pitch::to_erb(*self)
+    }
+
+    /// Convert to Perc.
+    #[deprecated(note = "use Pitch::to_perc")]
+    #[inline]
+    pub fn to_perc(&self) -> Perc {
+        Pitch::to_perc(*self)
+    }
+
+    /// Convert to a floating point MIDI-esque Step.
+    #[deprecated(note = "use Pitch::to_step")]
+    #[inline]
+    pub fn to_step(&self) -> Step {
+        Pitch::to_step(*self)
+    }
+}
+
+impl<C: Curve> ScaledPerc<C> {
     /// Return the value as a scaled percentage.
     #[inline]
     pub fn scaled_perc(&self) -> calc::Perc {
-        let ScaledPerc(perc, _) = *self;
-        perc
+        self.0
     }
 
-    /// Return the scale weight.
+    /// Return the curve along which the percentage is scaled.
     #[inline]
-    pub fn scale_weight(&self) -> ScaleWeight {
-        let ScaledPerc(_, weight) = *self;
-        weight
+    pub fn curve(&self) -> &C {
+        &self.1
     }
 
     /// Convert to the unit value of the equivalent frequency in Hz.
     #[inline]
     pub fn hz(&self) -> calc::Hz {
-        let ScaledPerc(perc, weight) = *self;
-        hz_from_scaled_perc(perc, weight)
+        hz_from_perc(self.perc())
     }
 
-    /// Convert to the equivalent frequency in Hz.
+    /// Convert to hz, interpreting the value as a scaled percentage of the given frequency range.
     #[inline]
-    pub fn to_hz(&self) -> Hz {
-        Hz(self.hz())
+    pub fn hz_in_range(&self, range: FrequencyRange) -> calc::Hz {
+        hz_from_perc_in_range(self.perc(), range)
+    }
+
+    /// Convert to a Hz struct, interpreting the value as a scaled percentage of the given
+    /// frequency range.
+    #[inline]
+    pub fn to_hz_in_range(&self, range: FrequencyRange) -> Hz {
+        Hz(self.hz_in_range(range))
     }
 
     /// Convert to (Letter, Octave) tuple.
     #[inline]
     pub fn letter_octave(&self) -> (Letter, Octave) {
-        let ScaledPerc(perc, weight) = *self;
-        letter_octave_from_scaled_perc(perc, weight)
+        letter_octave_from_perc(self.perc())
     }
 
     /// Convert to Letter.
@@ -70,113 +144,89 @@ impl ScaledPerc {
         octave
     }
 
-    /// Convert to LetterOctave.
-    #[inline]
-    pub fn to_letter_octave(&self) -> LetterOctave {
-        let (letter, octave) = self.letter_octave();
-        LetterOctave(letter, octave)
-    }
-
     /// Convert to the unit value of a Mel.
     #[inline]
     pub fn mel(&self) -> calc::Mel {
-        let ScaledPerc(perc, weight) = *self;
-        mel_from_scaled_perc(perc, weight)
+        mel_from_perc(self.perc())
     }
 
-    /// Convert to a Mel struct.
+    /// Convert to the unit value of a Bark.
     #[inline]
-    pub fn to_mel(&self) -> Mel {
-        Mel(self.mel())
+    pub fn bark(&self) -> calc::Bark {
+        bark_from_hz(self.hz())
     }
 
-    /// Convert to the unit value of a Perc struct.
+    /// Convert to the unit value of an Erb.
     #[inline]
-    pub fn perc(&self) -> calc::Perc {
-        let ScaledPerc(perc, weight) = *self;
-        perc_from_scaled_perc(perc, weight)
+    pub fn erb(&self) -> calc::Erb {
+        erb_from_hz(self.hz())
     }
 
-    /// Convert to Perc.
+    /// Convert to the unit value of a Perc struct.
     #[inline]
-    pub fn to_perc(&self) -> Perc {
-        Perc(self.perc())
+    pub fn perc(&self) -> calc::Perc {
+        perc_from_scaled_perc_with_curve(self.scaled_perc(), self.curve())
     }
 
     /// Convert to the unit value of a Step.
     #[inline]
     pub fn step(&self) -> calc::Step {
-        let ScaledPerc(perc, weight) = *self;
-        step_from_scaled_perc(perc, weight)
-    }
-
-    /// Convert to a floating point MIDI-esque Step.
-    #[inline]
-    pub fn to_step(&self) -> Step {
-        Step(self.step())
+        step_from_perc(self.perc())
     }
 }
 
-impl Add for ScaledPerc {
-    type Output = ScaledPerc;
+impl<C: Curve> Add for ScaledPerc<C> {
+    type Output = ScaledPerc<C>;
     #[inline]
-    fn add(self, rhs: ScaledPerc) -> ScaledPerc {
-        (self.to_perc() + rhs.to_perc()).to_scaled_perc_with_weight(self.scale_weight())
+    fn add(self, rhs: ScaledPerc<C>) -> ScaledPerc<C> {
+        let perc = Perc(self.perc() + rhs.perc());
+        perc.to_scaled_perc_with_curve(self.1)
     }
 }
 
-impl Sub for ScaledPerc {
-    type Output = ScaledPerc;
+impl<C: Curve> Sub for ScaledPerc<C> {
+    type Output = ScaledPerc<C>;
     #[inline]
-    fn sub(self, rhs: ScaledPerc) -> ScaledPerc {
-        (self.to_perc() - rhs.to_perc()).to_scaled_perc_with_weight(self.scale_weight())
+    fn sub(self, rhs: ScaledPerc<C>) -> ScaledPerc<C> {
+        let perc = Perc(self.perc() - rhs.perc());
+        perc.to_scaled_perc_with_curve(self.1)
     }
 }
 
-impl Mul for ScaledPerc {
-    type Output = ScaledPerc;
+impl<C: Curve> Neg for ScaledPerc<C> {
+    type Output = ScaledPerc<C>;
     #[inline]
-    fn mul(self, rhs: ScaledPerc) -> ScaledPerc {
-        (self.to_perc() * rhs.to_perc()).to_scaled_perc_with_weight(self.scale_weight())
+    fn neg(self) -> ScaledPerc<C> {
+        ScaledPerc(-self.0, self.1)
     }
 }
 
-impl Div for ScaledPerc {
-    type Output = ScaledPerc;
+impl<C: Curve> PartialEq for ScaledPerc<C> {
     #[inline]
-    fn div(self, rhs: ScaledPerc) -> ScaledPerc {
-        (self.to_perc() / rhs.to_perc()).to_scaled_perc_with_weight(self.scale_weight())
+    fn eq(&self, other: &ScaledPerc<C>) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-impl Rem for ScaledPerc {
-    type Output = ScaledPerc;
-    #[inline]
-    fn rem(self, rhs: ScaledPerc) -> ScaledPerc {
-        (self.to_perc() % rhs.to_perc()).to_scaled_perc_with_weight(self.scale_weight())
-    }
-}
+impl<C: Curve> Eq for ScaledPerc<C> {}
 
-impl Neg for ScaledPerc {
-    type Output = ScaledPerc;
+impl<C: Curve> PartialOrd for ScaledPerc<C> {
     #[inline]
-    fn neg(self) -> ScaledPerc {
-        ScaledPerc(-self.scaled_perc(), self.scale_weight())
+    fn partial_cmp(&self, other: &ScaledPerc<C>) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl PartialEq for ScaledPerc {
+impl<C: Curve> Ord for ScaledPerc<C> {
     #[inline]
-    fn eq(&self, other: &ScaledPerc) -> bool {
-        self.perc() == other.perc()
+    fn cmp(&self, other: &ScaledPerc<C>) -> Ordering {
+        self.perc().total_cmp(&other.perc())
     }
 }
 
-impl Eq for ScaledPerc {}
-
-impl PartialOrd for ScaledPerc {
+impl<C: Curve> Hash for ScaledPerc<C> {
     #[inline]
-    fn partial_cmp(&self, other: &ScaledPerc) -> Option<Ordering> {
-        self.to_perc().partial_cmp(&other.to_perc())
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.perc().to_bits().hash(state);
     }
 }
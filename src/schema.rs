@@ -0,0 +1,184 @@
+//! JSON Schemas describing the default serde representation of each pitch type.
+//!
+//! The schemas include the constraints checked during deserialization, e.g. that a `Hz` is not
+//! negative and that the weight of a `ScaledPerc` is positive. Fields using the
+//! `serde::scientific` adapter are described by `serde::scientific::schema` instead, which also
+//! accepts the adapter's notation.
+
+use crate::{
+    calc, AnyPitch, Bark, Erb, FrequencyRange, Hz, Letter, LetterOctave, Mel, MelFormula, Octave,
+    Perc, ScaledPerc, Step,
+};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+/// Implement `JsonSchema` for each type with the given name and schema.
+macro_rules! impl_json_schema {
+    ($($T:ty => $name:literal, |$generator:ident| $schema:expr;)*) => {
+        $(
+            impl JsonSchema for $T {
+                fn schema_name() -> Cow<'static, str> {
+                    $name.into()
+                }
+
+                fn schema_id() -> Cow<'static, str> {
+                    concat!("pitch_calc::", $name).into()
+                }
+
+                fn json_schema($generator: &mut SchemaGenerator) -> Schema {
+                    $schema
+                }
+            }
+        )*
+    };
+}
+
+/// The same schema describes a mel on any scale, as the scale is not serialized.
+impl<S: MelFormula> JsonSchema for Mel<S> {
+    fn schema_name() -> Cow<'static, str> {
+        "Mel".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "pitch_calc::Mel".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "A pitch in mels.",
+            "type": "number",
+            "minimum": 0,
+        })
+    }
+}
+
+impl_json_schema! {
+    Hz => "Hz", |_generator| json_schema!({
+        "description": "A frequency in hz.",
+        "type": "number",
+        "minimum": 0,
+    });
+    Bark => "Bark", |_generator| json_schema!({
+        "description": "A pitch on the Bark scale.",
+        "type": "number",
+        "minimum": Hz(0.0).bark(),
+    });
+    Erb => "Erb", |_generator| json_schema!({
+        "description": "A pitch as an ERB number.",
+        "type": "number",
+        "minimum": 0,
+    });
+    Step => "Step", |_generator| json_schema!({
+        "description": "A MIDI-esque step, where 69.0 is A4.",
+        "type": "number",
+    });
+    Perc => "Perc", |_generator| json_schema!({
+        "description": "A percentage of the human hearing range, where 0.0 is 20hz and 1.0 is 20khz.",
+        "type": "number",
+        "minimum": Hz(0.0).perc(),
+    });
+    ScaledPerc => "ScaledPerc", |_generator| json_schema!({
+        "description": "A scaled percentage of the human hearing range and its positive scale weight.",
+        "type": "array",
+        "prefixItems": [
+            { "type": "number", "minimum": 0 },
+            { "type": "number", "exclusiveMinimum": 0 },
+        ],
+        "minItems": 2,
+        "maxItems": 2,
+    });
+    Letter => "Letter", |_generator| json_schema!({
+        "description": "A note letter, where sharps are suffixed with \"sh\" and flats with \"b\".",
+        "type": "string",
+        "enum": [
+            "C", "Csh", "Db", "D", "Dsh", "Eb", "E", "F", "Fsh", "Gb", "G", "Gsh", "Ab", "A",
+            "Ash", "Bb", "B",
+        ],
+    });
+    LetterOctave => "LetterOctave", |generator| json_schema!({
+        "description": "A note letter and octave, where [\"A\", 4] is A4.",
+        "type": "array",
+        "prefixItems": [
+            generator.subschema_for::<Letter>(),
+            { "type": "integer", "minimum": MIN_OCTAVE, "maximum": MAX_OCTAVE },
+        ],
+        "minItems": 2,
+        "maxItems": 2,
+    });
+    FrequencyRange => "FrequencyRange", |_generator| json_schema!({
+        "description": "A range of frequencies in hz, where 0 < min < max.",
+        "type": "object",
+        "properties": {
+            "min": { "type": "number", "exclusiveMinimum": 0 },
+            "max": { "type": "number", "exclusiveMinimum": 0 },
+        },
+        "required": ["min", "max"],
+    });
+    AnyPitch => "AnyPitch", |generator| {
+        let variant = |name: &str, schema: Schema| json_schema!({
+            "type": "object",
+            "properties": { name: schema },
+            "required": [name],
+            "additionalProperties": false,
+        });
+        json_schema!({
+            "description": "A pitch in any representation, keyed by the name of the representation.",
+            "oneOf": [
+                variant("Hz", generator.subschema_for::<Hz>()),
+                variant("Mel", generator.subschema_for::<Mel>()),
+                variant("Bark", generator.subschema_for::<Bark>()),
+                variant("Erb", generator.subschema_for::<Erb>()),
+                variant("Step", generator.subschema_for::<Step>()),
+                variant("Perc", generator.subschema_for::<Perc>()),
+                variant("ScaledPerc", generator.subschema_for::<ScaledPerc>()),
+                variant("LetterOctave", generator.subschema_for::<LetterOctave>()),
+            ],
+        })
+    };
+}
+
+/// The lowest and highest octaves in which every letter may be represented as a step.
+const MIN_OCTAVE: Octave = -(calc::MAX_STEP as Octave / 12) - 1;
+const MAX_OCTAVE: Octave = (calc::MAX_STEP as Octave - 11) / 12 - 1;
+
+/// A pitch in the `scientific` notation, with the given example, or its default representation.
+pub(crate) fn notation_or_value<T: JsonSchema>(
+    generator: &mut SchemaGenerator,
+    example: &str,
+) -> Schema {
+    json_schema!({
+        "anyOf": [
+            { "type": "string", "examples": [example] },
+            generator.subschema_for::<T>(),
+        ],
+    })
+}
+
+/// A `ScaledPerc` pair whose percentage may be in the `scientific` notation, e.g. `["50%", 4.0]`.
+pub(crate) fn scaled_perc_notation(generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "anyOf": [
+            {
+                "type": "array",
+                "prefixItems": [
+                    { "type": "string", "examples": ["50%"] },
+                    { "type": "number", "exclusiveMinimum": 0 },
+                ],
+                "minItems": 2,
+                "maxItems": 2,
+            },
+            generator.subschema_for::<ScaledPerc>(),
+        ],
+    })
+}
+
+/// Any pitch in the `scientific` notation, or the default representation of an `AnyPitch`.
+pub(crate) fn any_pitch_notation(generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "anyOf": [
+            { "type": "string", "examples": ["A4", "440 Hz", "69m"] },
+            scaled_perc_notation(generator),
+            generator.subschema_for::<AnyPitch>(),
+        ],
+    })
+}
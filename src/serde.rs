@@ -0,0 +1,611 @@
+//! Serde adapters for writing pitches in the notation used by the `FromStr` implementations.
+//!
+//! By default pitches serialize as their raw values, e.g. `LetterOctave(Letter::A, 4)` as
+//! `["A", 4]`. Fields annotated with `#[serde(with = "pitch_calc::serde::scientific")]` are instead
+//! written as `"A4"`, `"C#5"` or `"440 Hz"` by human-readable formats such as JSON and YAML, while
+//! binary formats (those whose `is_human_readable` is false) keep the compact default
+//! representation.
+//!
+//! | Type           | Notation              |
+//! |----------------|-----------------------|
+//! | `Letter`       | `"C#"`                |
+//! | `LetterOctave` | `"A4"`                |
+//! | `Hz`           | `"440 Hz"`            |
+//! | `Step`         | `"69m"`               |
+//! | `Mel`          | `"549.64 mel"`        |
+//! | `Bark`         | `"4.25 Bark"`         |
+//! | `Erb`          | `"12.8 ERB"`          |
+//! | `Perc`         | `"50%"`               |
+//! | `ScaledPerc`   | `["50%", 4.0]`        |
+//! | `AnyPitch`     | any of the above      |
+//!
+//! `Option`s and `Vec`s of pitches are written as `null` or a sequence of the above.
+//!
+//! Numbers are written with enough precision to be read back exactly. When reading, units are case
+//! insensitive, and every continuous representation also accepts any frequency understood by `Hz`,
+//! e.g. a `Mel` may be given as `"A4"` or `"1.2 kHz"`. The default representation is accepted too,
+//! so a hand-written `440.0` or `["A", 4]` reads the same as without the adapter.
+//!
+//! With the `schemars` feature, `scientific::schema` describes both forms, for use with
+//! `#[schemars(schema_with = "pitch_calc::serde::scientific::schema::<Hz>")]` alongside the adapter.
+
+use crate::{
+    calc, AnyPitch, Bark, BinMapper, Curve, Erb, FrequencyRange, Hz, Letter, LetterOctave, Mel,
+    MelFormula, Octave, ParsePitchError, Perc, Pitch, PitchError, ScaleWeight, ScaledPerc, Step,
+};
+use ::serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use ::serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use ::serde::ser::Serializer;
+use ::serde::{Deserialize, Serialize};
+use num_traits::Float;
+#[cfg(feature = "schemars")]
+use schemars::{Schema, SchemaGenerator};
+use std::fmt;
+use std::str::FromStr;
+
+/// A pitch with a human-readable notation, as used by the `scientific` adapter.
+pub trait ScientificNotation: Sized {
+    /// Serialize the pitch in its notation.
+    fn serialize_notation<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    /// Deserialize the pitch from its notation or its default representation.
+    fn deserialize_notation<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+
+    /// The JSON Schema of the values accepted by `deserialize_notation`, any string by default.
+    #[cfg(feature = "schemars")]
+    fn notation_schema(_generator: &mut SchemaGenerator) -> Schema {
+        schemars::json_schema!({ "type": "string" })
+    }
+}
+
+/// The adapter for use with `#[serde(with = "pitch_calc::serde::scientific")]`.
+pub mod scientific {
+    use super::ScientificNotation;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize the pitch in its notation if the format is human-readable, otherwise compactly.
+    pub fn serialize<T, S>(pitch: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ScientificNotation + Serialize,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            pitch.serialize_notation(serializer)
+        } else {
+            pitch.serialize(serializer)
+        }
+    }
+
+    /// Deserialize the pitch from its notation if the format is human-readable, otherwise from its
+    /// compact representation.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: ScientificNotation + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            T::deserialize_notation(deserializer)
+        } else {
+            T::deserialize(deserializer)
+        }
+    }
+
+    /// The JSON Schema of a field using the adapter, which accepts either the notation or the
+    /// default representation.
+    #[cfg(feature = "schemars")]
+    pub fn schema<T: ScientificNotation>(
+        generator: &mut schemars::SchemaGenerator,
+    ) -> schemars::Schema {
+        T::notation_schema(generator)
+    }
+}
+
+/// Implement `ScientificNotation` for pitches written as a single string, and read from either
+/// the string or their default representation.
+macro_rules! impl_string_notation {
+    ($($T:ident: |$pitch:ident| $display:expr, $parse:expr, $from_number:expr, $example:literal;)*) => {
+        $(
+            impl ScientificNotation for $T {
+                fn serialize_notation<S: Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    let $pitch = *self;
+                    serializer.collect_str(&$display)
+                }
+
+                fn deserialize_notation<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<$T, D::Error> {
+                    let pitch = deserializer.deserialize_any(NotationVisitor {
+                        parse: $parse,
+                        from_number: $from_number,
+                        expecting: concat!("a pitch such as \"", $example, "\""),
+                    })?;
+                    pitch.validate().map_err(de::Error::custom)
+                }
+
+                #[cfg(feature = "schemars")]
+                fn notation_schema(generator: &mut SchemaGenerator) -> Schema {
+                    crate::schema::notation_or_value::<$T>(generator, $example)
+                }
+            }
+        )*
+    };
+}
+
+impl_string_notation! {
+    Letter: |letter| letter, Letter::from_str, None, "C#";
+    LetterOctave: |letter_octave| letter_octave, LetterOctave::from_str, None, "A4";
+    Hz: |hz| format_args!("{} Hz", hz.hz()), Hz::from_str, Some(|n| Hz(n as calc::Hz)), "440 Hz";
+    Step: |step| format_args!("{}m", step.step()),
+        |s| parse_unit(s, "m", Step), Some(|n| Step(n as calc::Step)), "69m";
+    Bark: |bark| format_args!("{} Bark", bark.bark()),
+        |s| parse_unit(s, "bark", Bark), Some(|n| Bark(n as calc::Bark)), "4.25 Bark";
+    Erb: |erb| format_args!("{} ERB", erb.erb()),
+        |s| parse_unit(s, "erb", Erb), Some(|n| Erb(n as calc::Erb)), "12.8 ERB";
+    Perc: |perc| format_args!("{}%", perc.perc() * 100.0), parse_perc, Some(Perc), "50%";
+}
+
+impl<M: MelFormula> ScientificNotation for Mel<M> {
+    fn serialize_notation<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{} mel", self.mel()))
+    }
+
+    fn deserialize_notation<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Mel<M>, D::Error> {
+        let pitch = deserializer.deserialize_any(NotationVisitor {
+            parse: |s| parse_unit(s, "mel", |mel| Mel(mel, M::default())),
+            from_number: Some(|mel| Mel(mel as calc::Mel, M::default())),
+            expecting: "a pitch such as \"549.64 mel\"",
+        })?;
+        pitch.validate().map_err(de::Error::custom)
+    }
+
+    #[cfg(feature = "schemars")]
+    fn notation_schema(generator: &mut SchemaGenerator) -> Schema {
+        crate::schema::notation_or_value::<Mel<M>>(generator, "549.64 mel")
+    }
+}
+
+impl ScientificNotation for ScaledPerc {
+    fn serialize_notation<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let ScaledPerc(perc, weight) = *self;
+        (format!("{}%", perc * 100.0), weight).serialize(serializer)
+    }
+
+    fn deserialize_notation<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ScaledPerc, D::Error> {
+        let (perc, weight) = <(PercNotation, ScaleWeight)>::deserialize(deserializer)?;
+        let scaled = perc.with_weight(weight).map_err(de::Error::custom)?;
+        scaled.validate().map_err(de::Error::custom)
+    }
+
+    #[cfg(feature = "schemars")]
+    fn notation_schema(generator: &mut SchemaGenerator) -> Schema {
+        crate::schema::scaled_perc_notation(generator)
+    }
+}
+
+impl ScientificNotation for AnyPitch {
+    fn serialize_notation<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            AnyPitch::Hz(hz) => hz.serialize_notation(serializer),
+            AnyPitch::Mel(mel) => mel.serialize_notation(serializer),
+            AnyPitch::Bark(bark) => bark.serialize_notation(serializer),
+            AnyPitch::Erb(erb) => erb.serialize_notation(serializer),
+            AnyPitch::Step(step) => step.serialize_notation(serializer),
+            AnyPitch::Perc(perc) => perc.serialize_notation(serializer),
+            AnyPitch::ScaledPerc(scaled) => scaled.serialize_notation(serializer),
+            AnyPitch::LetterOctave(letter_octave) => letter_octave.serialize_notation(serializer),
+        }
+    }
+
+    fn deserialize_notation<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<AnyPitch, D::Error> {
+        let pitch = deserializer.deserialize_any(AnyPitchVisitor)?;
+        pitch.validate().map_err(de::Error::custom)
+    }
+
+    #[cfg(feature = "schemars")]
+    fn notation_schema(generator: &mut SchemaGenerator) -> Schema {
+        crate::schema::any_pitch_notation(generator)
+    }
+}
+
+impl<T: ScientificNotation> ScientificNotation for Option<T> {
+    fn serialize_notation<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Some(pitch) => serializer.serialize_some(&Notation(pitch)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    fn deserialize_notation<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        let pitch = Option::<Notation<T>>::deserialize(deserializer)?;
+        Ok(pitch.map(|Notation(pitch)| pitch))
+    }
+
+    #[cfg(feature = "schemars")]
+    fn notation_schema(generator: &mut SchemaGenerator) -> Schema {
+        schemars::json_schema!({ "anyOf": [{ "type": "null" }, T::notation_schema(generator)] })
+    }
+}
+
+impl<T: ScientificNotation> ScientificNotation for Vec<T> {
+    fn serialize_notation<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Notation))
+    }
+
+    fn deserialize_notation<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        let pitches = Vec::<Notation<T>>::deserialize(deserializer)?;
+        Ok(pitches.into_iter().map(|Notation(pitch)| pitch).collect())
+    }
+
+    #[cfg(feature = "schemars")]
+    fn notation_schema(generator: &mut SchemaGenerator) -> Schema {
+        schemars::json_schema!({ "type": "array", "items": T::notation_schema(generator) })
+    }
+}
+
+/// Serializes and deserializes the wrapped pitch in its notation, for use within collections.
+struct Notation<T>(T);
+
+impl<T: ScientificNotation> Serialize for Notation<&T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_notation(serializer)
+    }
+}
+
+impl<'de, T: ScientificNotation> Deserialize<'de> for Notation<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Notation<T>, D::Error> {
+        T::deserialize_notation(deserializer).map(Notation)
+    }
+}
+
+/// Reads a pitch from its notation, or from its default representation as a number or sequence.
+struct NotationVisitor<T> {
+    parse: fn(&str) -> Result<T, ParsePitchError>,
+    from_number: Option<fn(f64) -> T>,
+    expecting: &'static str,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for NotationVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        (self.parse)(s).map_err(E::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<T, E> {
+        match self.from_number {
+            Some(from_number) => Ok(from_number(value)),
+            None => Err(E::invalid_type(de::Unexpected::Float(value), &self)),
+        }
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        self.visit_f64(value as f64)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        self.visit_f64(value as f64)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<T, A::Error> {
+        T::deserialize(SeqAccessDeserializer::new(seq))
+    }
+}
+
+/// The percentage of a `ScaledPerc` pair, either in notation or as a number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PercNotation {
+    Value(calc::Perc),
+    Notation(String),
+}
+
+impl PercNotation {
+    fn with_weight(self, weight: ScaleWeight) -> Result<ScaledPerc, ParsePitchError> {
+        match self {
+            PercNotation::Value(perc) => Ok(ScaledPerc(perc, weight)),
+            PercNotation::Notation(perc) => scaled_perc(&perc, weight),
+        }
+    }
+}
+
+/// Reads an `AnyPitch` from a string, the `ScaledPerc` pair or its default representation.
+struct AnyPitchVisitor;
+
+impl<'de> Visitor<'de> for AnyPitchVisitor {
+    type Value = AnyPitch;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(
+            "a pitch such as \"A4\", \"440 Hz\" or \"69m\", a [\"50%\", weight] pair or a map",
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<AnyPitch, E> {
+        parse_any_pitch(s).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<AnyPitch, A::Error> {
+        let perc: PercNotation = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let weight: ScaleWeight = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        perc.with_weight(weight)
+            .map(AnyPitch::ScaledPerc)
+            .map_err(de::Error::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<AnyPitch, A::Error> {
+        AnyPitch::deserialize(MapAccessDeserializer::new(map))
+    }
+}
+
+/// The number preceding `unit` in `s`, if `s` ends with `unit` (ignoring ASCII case).
+fn strip_unit<'a>(s: &'a str, unit: &str) -> Option<&'a str> {
+    let s = s.trim();
+    let split = s.len().checked_sub(unit.len())?;
+    match s.get(split..) {
+        Some(suffix) if suffix.eq_ignore_ascii_case(unit) => Some(s[..split].trim_end()),
+        _ => None,
+    }
+}
+
+/// Parse a number followed by `unit`, e.g. `"549.6 mel"`, or any frequency understood by `Hz`.
+fn parse_unit<P, N>(
+    s: &str,
+    unit: &str,
+    from_number: impl FnOnce(N) -> P,
+) -> Result<P, ParsePitchError>
+where
+    P: Pitch,
+    N: Float + FromStr,
+{
+    match strip_unit(s, unit) {
+        Some(number) => number
+            .parse::<N>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(from_number)
+            .ok_or_else(|| ParsePitchError::invalid(s)),
+        None => s.parse::<Hz>().map(P::from_hz),
+    }
+}
+
+/// Parse a percentage, e.g. `"50%"`, or any frequency understood by `Hz`.
+fn parse_perc(s: &str) -> Result<Perc, ParsePitchError> {
+    parse_unit(s, "%", |percent: f64| Perc(percent / 100.0))
+}
+
+/// Parse the percentage of a `ScaledPerc` with the given weight.
+fn scaled_perc(s: &str, weight: ScaleWeight) -> Result<ScaledPerc, ParsePitchError> {
+    match strip_unit(s, "%") {
+        Some(_) => parse_perc(s).map(|perc| ScaledPerc(perc.perc(), weight)),
+        None => s
+            .parse::<Hz>()
+            .map(|hz| hz.to_scaled_perc_with_weight(weight)),
+    }
+}
+
+/// Parse a pitch in any notation, choosing the representation by its unit.
+///
+/// Notes and frequencies become `LetterOctave` when they name an exact note and `Hz` otherwise.
+fn parse_any_pitch(s: &str) -> Result<AnyPitch, ParsePitchError> {
+    let has_unit = |unit| strip_unit(s, unit).is_some();
+    Ok(if has_unit("%") {
+        AnyPitch::Perc(parse_perc(s)?)
+    } else if has_unit("mel") {
+        AnyPitch::Mel(parse_unit(s, "mel", Mel::new)?)
+    } else if has_unit("bark") {
+        AnyPitch::Bark(parse_unit(s, "bark", Bark)?)
+    } else if has_unit("erb") {
+        AnyPitch::Erb(parse_unit(s, "erb", Erb)?)
+    } else if let Ok(letter_octave) = s.parse::<LetterOctave>() {
+        AnyPitch::LetterOctave(letter_octave)
+    } else if has_unit("m") {
+        AnyPitch::Step(parse_unit(s, "m", Step)?)
+    } else {
+        AnyPitch::Hz(s.parse()?)
+    })
+}
+
+/// Checks a deserialized pitch, rejecting values that do not describe a valid pitch.
+pub(crate) trait Validate: Sized {
+    fn validate(self) -> Result<Self, PitchError>;
+}
+
+/// Check that the value is finite and its frequency is not negative.
+fn validate_hz(value: f64, hz: calc::Hz) -> Result<(), PitchError> {
+    if !value.is_finite() || !hz.is_finite() {
+        Err(PitchError::NonFinite)
+    } else if hz < 0.0 {
+        Err(PitchError::NegativeFrequency(hz))
+    } else {
+        Ok(())
+    }
+}
+
+impl Validate for Letter {
+    fn validate(self) -> Result<Letter, PitchError> {
+        Ok(self)
+    }
+}
+
+impl Validate for LetterOctave {
+    fn validate(self) -> Result<LetterOctave, PitchError> {
+        let LetterOctave(letter, octave) = self;
+        calc::try_step_from_letter_octave(letter, octave).map(|_| self)
+    }
+}
+
+impl Validate for Hz {
+    fn validate(self) -> Result<Hz, PitchError> {
+        validate_hz(self.hz().into(), self.hz()).map(|_| self)
+    }
+}
+
+/// Implement `Validate` for pitches with an inherent `hz` and a unit value of type `$value`.
+macro_rules! impl_validate {
+    ($($T:ident => $value:ident),*) => {
+        $(
+            impl Validate for $T {
+                fn validate(self) -> Result<$T, PitchError> {
+                    validate_hz(self.$value().into(), self.hz()).map(|_| self)
+                }
+            }
+        )*
+    };
+}
+
+impl<S: MelFormula> Validate for Mel<S> {
+    fn validate(self) -> Result<Mel<S>, PitchError> {
+        validate_hz(self.mel().into(), self.hz()).map(|_| self)
+    }
+}
+
+impl_validate!(Bark => bark, Erb => erb, Step => step, Perc => perc);
+
+impl<C: Curve> Validate for ScaledPerc<C> {
+    fn validate(self) -> Result<ScaledPerc<C>, PitchError> {
+        self.curve().validate()?;
+        // Check the scaled percentage before it is unscaled, as e.g. an even weight maps negative
+        // percentages to positive ones.
+        let scaled = self.scaled_perc();
+        if scaled < 0.0 {
+            return Err(PitchError::NegativeScaledPerc(scaled));
+        }
+        validate_hz(scaled, self.hz()).map(|_| self)
+    }
+}
+
+impl Validate for AnyPitch {
+    fn validate(self) -> Result<AnyPitch, PitchError> {
+        match self {
+            AnyPitch::Hz(hz) => hz.validate().map(AnyPitch::Hz),
+            AnyPitch::Mel(mel) => mel.validate().map(AnyPitch::Mel),
+            AnyPitch::Bark(bark) => bark.validate().map(AnyPitch::Bark),
+            AnyPitch::Erb(erb) => erb.validate().map(AnyPitch::Erb),
+            AnyPitch::Step(step) => step.validate().map(AnyPitch::Step),
+            AnyPitch::Perc(perc) => perc.validate().map(AnyPitch::Perc),
+            AnyPitch::ScaledPerc(scaled) => scaled.validate().map(AnyPitch::ScaledPerc),
+            AnyPitch::LetterOctave(letter_octave) => {
+                letter_octave.validate().map(AnyPitch::LetterOctave)
+            }
+        }
+    }
+}
+
+/// Declare the unvalidated representations from which the newtype pitches are deserialized.
+///
+/// Each is renamed after its pitch type, so that the serialized form is unchanged.
+macro_rules! impl_newtype_repr {
+    ($($Repr:ident => $T:ident($value:ty): $name:literal;)*) => {
+        $(
+            #[derive(Deserialize)]
+            #[serde(rename = $name)]
+            pub(crate) struct $Repr($value);
+
+            impl TryFrom<$Repr> for $T {
+                type Error = PitchError;
+                fn try_from(repr: $Repr) -> Result<$T, PitchError> {
+                    $T(repr.0).validate()
+                }
+            }
+        )*
+    };
+}
+
+impl_newtype_repr! {
+    HzRepr => Hz(calc::Hz): "Hz";
+    BarkRepr => Bark(calc::Bark): "Bark";
+    ErbRepr => Erb(calc::Erb): "Erb";
+    StepRepr => Step(calc::Step): "Step";
+    PercRepr => Perc(calc::Perc): "Perc";
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Mel")]
+pub(crate) struct MelRepr(calc::Mel);
+
+impl<S: MelFormula> TryFrom<MelRepr> for Mel<S> {
+    type Error = PitchError;
+    fn try_from(repr: MelRepr) -> Result<Mel<S>, PitchError> {
+        Mel(repr.0, S::default()).validate()
+    }
+}
+
+impl<S: MelFormula> From<Mel<S>> for MelRepr {
+    fn from(mel: Mel<S>) -> MelRepr {
+        MelRepr(mel.mel())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "LetterOctave")]
+pub(crate) struct LetterOctaveRepr(Letter, Octave);
+
+impl TryFrom<LetterOctaveRepr> for LetterOctave {
+    type Error = PitchError;
+    fn try_from(
+        LetterOctaveRepr(letter, octave): LetterOctaveRepr,
+    ) -> Result<LetterOctave, PitchError> {
+        LetterOctave(letter, octave).validate()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "ScaledPerc")]
+pub(crate) struct ScaledPercRepr<C>(calc::Perc, C);
+
+impl<C: Curve> TryFrom<ScaledPercRepr<C>> for ScaledPerc<C> {
+    type Error = PitchError;
+    fn try_from(
+        ScaledPercRepr(perc, curve): ScaledPercRepr<C>,
+    ) -> Result<ScaledPerc<C>, PitchError> {
+        ScaledPerc(perc, curve).validate()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "FrequencyRange")]
+pub(crate) struct FrequencyRangeRepr {
+    min: calc::Hz,
+    max: calc::Hz,
+}
+
+impl TryFrom<FrequencyRangeRepr> for FrequencyRange {
+    type Error = PitchError;
+    fn try_from(repr: FrequencyRangeRepr) -> Result<FrequencyRange, PitchError> {
+        FrequencyRange::new(repr.min, repr.max)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "BinMapper")]
+pub(crate) struct BinMapperRepr {
+    sample_rate: calc::Hz,
+    fft_size: usize,
+}
+
+impl TryFrom<BinMapperRepr> for BinMapper {
+    type Error = PitchError;
+    fn try_from(repr: BinMapperRepr) -> Result<BinMapper, PitchError> {
+        BinMapper::new(repr.sample_rate, repr.fft_size)
+    }
+}
@@ -1,22 +1,26 @@
 use super::{
-    calc, hz_from_step, letter_octave_from_step, mel_from_step, perc_from_step,
-    scaled_perc_from_step, Hz, Letter, LetterOctave, Mel, Octave, Perc, ScaleWeight, ScaledPerc,
-    DEFAULT_SCALE_WEIGHT,
+    bark_from_hz, calc, erb_from_hz, hz_from_step, letter_octave_from_step, mel_from_step,
+    perc_from_step, scaled_perc_from_step, Bark, Erb, Hz, Interval, Letter, LetterOctave, Mel,
+    Octave, Perc, Pitch, ScaleWeight, ScaledPerc, DEFAULT_SCALE_WEIGHT,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Neg, Sub};
 
 /// Pitch representation in the form of a MIDI-esque Step.
+///
+/// Ordered and hashed by the step with `f32::total_cmp`, so NaN sorts last and equals itself.
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde::StepRepr"))]
 pub struct Step(pub calc::Step);
 
 impl Step {
     /// Return the value in steps.
     #[inline]
-    pub fn step(self) -> calc::Step {
+    pub const fn step(self) -> calc::Step {
         let Step(step) = self;
         step
     }
@@ -29,9 +33,10 @@ impl Step {
     }
 
     /// Convert to the equivalent frequency in Hz.
+    #[deprecated(note = "use Pitch::to_hz")]
     #[inline]
     pub fn to_hz(self) -> Hz {
-        Hz(self.hz())
+        Pitch::to_hz(self)
     }
 
     /// Convert to the closest equivalent (Letter, Octave).
@@ -55,10 +60,10 @@ impl Step {
     }
 
     /// Convert to the closest equivalent LetterOctave.
+    #[deprecated(note = "use Pitch::to_letter_octave")]
     #[inline]
     pub fn to_letter_octave(self) -> LetterOctave {
-        let (letter, octave) = self.letter_octave();
-        LetterOctave(letter, octave)
+        Pitch::to_letter_octave(self)
     }
 
     /// Convert to a Mel unit value.
@@ -68,9 +73,36 @@ impl Step {
     }
 
     /// Convert to a Mel struct.
+    #[deprecated(note = "use Pitch::to_mel")]
     #[inline]
     pub fn to_mel(self) -> Mel {
-        Mel(self.mel())
+        Pitch::to_mel(self)
+    }
+
+    /// Convert to the unit value of a Bark.
+    #[inline]
+    pub fn bark(self) -> calc::Bark {
+        bark_from_hz(self.hz())
+    }
+
+    /// Convert to a Bark struct.
+    #[deprecated(note = "use Pitch::to_bark")]
+    #[inline]
+    pub fn to_bark(self) -> Bark {
+        Pitch::to_bark(self)
+    }
+
+    /// Convert to the unit value of an Erb.
+    #[inline]
+    pub fn erb(self) -> calc::Erb {
+        erb_from_hz(self.hz())
+    }
+
+    /// Convert to an Erb struct.
+    #[deprecated(note = "use Pitch::to_erb")]
+    #[inline]
+    pub fn to_erb(self) -> Erb {
+        Pitch::to_erb(self)
     }
 
     /// Convert to the unit value of the equivalent Perc.
@@ -80,9 +112,10 @@ impl Step {
     }
 
     /// Convert to a percentage of the human hearing range.
+    #[deprecated(note = "use Pitch::to_perc")]
     #[inline]
     pub fn to_perc(self) -> Perc {
-        Perc(self.perc())
+        Pitch::to_perc(self)
     }
 
     /// Convert to a scaled percentage of the human hearing range with a given weight.
@@ -104,9 +137,10 @@ impl Step {
     }
 
     /// Convert to a scaled percentage of the human hearing range.
+    #[deprecated(note = "use Pitch::to_scaled_perc")]
     #[inline]
     pub fn to_scaled_perc(self) -> ScaledPerc {
-        self.to_scaled_perc_with_weight(DEFAULT_SCALE_WEIGHT)
+        Pitch::to_scaled_perc(self)
     }
 }
 
@@ -126,42 +160,50 @@ impl Sub for Step {
     }
 }
 
-impl Mul for Step {
+impl Neg for Step {
     type Output = Step;
     #[inline]
-    fn mul(self, rhs: Step) -> Step {
-        Step(self.step() * rhs.step())
+    fn neg(self) -> Step {
+        Step(-self.step())
     }
 }
 
-impl Div for Step {
+impl Add<Interval> for Step {
     type Output = Step;
     #[inline]
-    fn div(self, rhs: Step) -> Step {
-        Step(self.step() / rhs.step())
+    fn add(self, rhs: Interval) -> Step {
+        Step(self.step() + rhs.semitones() as calc::Step)
     }
 }
 
-impl Rem for Step {
+impl Sub<Interval> for Step {
     type Output = Step;
     #[inline]
-    fn rem(self, rhs: Step) -> Step {
-        Step(self.step() % rhs.step())
+    fn sub(self, rhs: Interval) -> Step {
+        Step(self.step() - rhs.semitones() as calc::Step)
     }
 }
 
-impl Neg for Step {
+impl Add<calc::Semitones> for Step {
     type Output = Step;
     #[inline]
-    fn neg(self) -> Step {
-        Step(-self.step())
+    fn add(self, rhs: calc::Semitones) -> Step {
+        Step(self.step() + rhs as calc::Step)
+    }
+}
+
+impl Sub<calc::Semitones> for Step {
+    type Output = Step;
+    #[inline]
+    fn sub(self, rhs: calc::Semitones) -> Step {
+        Step(self.step() - rhs as calc::Step)
     }
 }
 
 impl PartialEq for Step {
     #[inline]
     fn eq(&self, other: &Step) -> bool {
-        self.step() == other.step()
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -177,7 +219,14 @@ impl PartialOrd for Step {
 impl Ord for Step {
     #[inline]
     fn cmp(&self, other: &Step) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.step().total_cmp(&other.step())
+    }
+}
+
+impl Hash for Step {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.step().to_bits().hash(state);
     }
 }
 
@@ -228,4 +277,35 @@ mod tests {
     }
 
     t!(u8, u16, i8, i16);
+
+    #[test]
+    fn semitone_arithmetic() {
+        use crate::Interval;
+
+        assert_eq!(Step(60.5) + 7, Step(67.5));
+        assert_eq!(Step(60.0) - 12, Step(48.0));
+        assert_eq!(Step(60.0) + 7, Step(60.0) + Interval::PERFECT_FIFTH);
+    }
+
+    #[test]
+    fn total_order() {
+        use std::collections::{BTreeMap, HashSet};
+
+        let mut steps = [
+            Step(f32::NAN),
+            Step(64.0),
+            Step(-0.0),
+            Step(0.0),
+            Step(-12.0),
+        ];
+        steps.sort();
+        assert_eq!(steps[..4], [Step(-12.0), Step(-0.0), Step(0.0), Step(64.0)]);
+        assert!(steps[4].step().is_nan());
+        assert_eq!(Step(f32::NAN), Step(f32::NAN));
+
+        let set: HashSet<Step> = steps.iter().cloned().chain(steps.iter().cloned()).collect();
+        assert_eq!(set.len(), steps.len());
+        let map: BTreeMap<Step, usize> = steps.iter().cloned().zip(0..).collect();
+        assert_eq!(map[&Step(f32::NAN)], 4);
+    }
 }
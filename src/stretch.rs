@@ -0,0 +1,421 @@
+//! Stretch tuning for the 88 keys of a piano.
+//!
+//! The partials of piano strings are sharpened by their stiffness (see the `harmonic` module), so
+//! octaves tuned beatless by ear are slightly wider than 2:1. Accumulated over the keyboard this
+//! flattens the bass and sharpens the treble relative to 12-TET, as measured by Railsback (1938).
+//!
+//! The stretch may be found by matching octaves, by minimising the entropy of the combined
+//! spectrum of the keys as proposed by Hinrichsen (2012), or approximated by the Railsback curve.
+
+use crate::harmonic::partial_hz;
+use crate::{
+    calc, hz_from_step_with_tuning, step_from_hz_with_tuning, Hz, Letter, LetterOctave,
+    DEFAULT_TUNING,
+};
+
+/// The number of keys on a standard piano.
+pub const PIANO_KEYS: usize = 88;
+/// The lowest key on a standard piano.
+pub const LOWEST_KEY: LetterOctave = LetterOctave(Letter::A, 0);
+/// The highest key on a standard piano.
+pub const HIGHEST_KEY: LetterOctave = LetterOctave(Letter::C, 8);
+
+/// The index of A4 among the piano keys.
+const A4_KEY: usize = 48;
+/// The index of A3 among the piano keys, the bottom of the octave in which the temperament is set.
+const A3_KEY: usize = A4_KEY - 12;
+
+/// The key at the given index from `0` (A0) to `87` (C8).
+///
+/// Panics if `index` is not less than `PIANO_KEYS`.
+#[inline]
+pub const fn piano_key(index: usize) -> LetterOctave {
+    assert!(index < PIANO_KEYS, "piano key index out of range");
+    LetterOctave::from_semitones(LOWEST_KEY.semitones() + index as calc::Semitones)
+}
+
+/// The index of the given key from `0` (A0) to `87` (C8), or `None` if it is not on the piano.
+#[inline]
+pub const fn piano_key_index(key: LetterOctave) -> Option<usize> {
+    let index = key.semitones() - LOWEST_KEY.semitones();
+    if index >= 0 && (index as usize) < PIANO_KEYS {
+        Some(index as usize)
+    } else {
+        None
+    }
+}
+
+/// The inharmonicity coefficient `B` of the string(s) of each key, from A0 to C8.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InharmonicityProfile(pub [f32; PIANO_KEYS]);
+
+impl InharmonicityProfile {
+    /// A profile in which every key has the same inharmonicity.
+    pub fn uniform(inharmonicity: f32) -> InharmonicityProfile {
+        InharmonicityProfile([inharmonicity; PIANO_KEYS])
+    }
+
+    /// A profile typical of a modern grand piano.
+    ///
+    /// Modelled as the sum of two exponentials, falling from ~2e-4 through the wound bass strings to
+    /// a minimum of ~1e-4 around C2, then rising to ~4e-4 at C4 and ~2e-2 at C8.
+    pub fn typical() -> InharmonicityProfile {
+        let mut profile = [0.0; PIANO_KEYS];
+        for (index, b) in profile.iter_mut().enumerate() {
+            let key = index as f32;
+            *b = 2.0e-4 * (-key / 12.0).exp() + 1.67e-5 * (key / 12.27).exp();
+        }
+        InharmonicityProfile(profile)
+    }
+
+    /// The inharmonicity of the given key, or `None` if it is not on the piano.
+    #[inline]
+    pub fn get(&self, key: LetterOctave) -> Option<f32> {
+        piano_key_index(key).map(|index| self.0[index])
+    }
+}
+
+/// How the stretch of each key is determined.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StretchMethod {
+    /// The temperament octave A3..A4 is divided evenly, and every other key is tuned so that its
+    /// `partial`th partial beats neither with the `2 * partial`th partial of the key an octave
+    /// below, nor with the `partial / 2`th partial of the key an octave above.
+    ///
+    /// `partial: 1` gives pure 2:1 octaves, while `partial: 2` gives the wider 4:2 octaves.
+    OctaveMatching { partial: u32 },
+    /// Every key but A4 is tuned to minimise the entropy of the combined spectrum of all keys, so
+    /// that as many of their partials as possible coincide.
+    ///
+    /// Each key contributes its first `partials` partials below 10khz, weighted by `1 / n`. Starting
+    /// from 2:1 octave matching, each key is moved in turn by ever smaller steps for as long as
+    /// this lowers the entropy.
+    Entropy { partials: u32 },
+    /// An approximation of the Railsback curve that ignores the inharmonicity profile.
+    ///
+    /// The deviation grows with the cube of the distance from A4, reaching `bass` cents at A0 and
+    /// `treble` cents at C8.
+    Railsback { bass: f32, treble: f32 },
+}
+
+/// The default Railsback approximation, roughly the average of Railsback's measurements.
+pub const DEFAULT_RAILSBACK: StretchMethod = StretchMethod::Railsback {
+    bass: -30.0,
+    treble: 35.0,
+};
+
+/// A single stretch-tuned key.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StretchedKey {
+    /// The key.
+    pub key: LetterOctave,
+    /// The stretched frequency of the key's fundamental.
+    pub hz: Hz,
+    /// The deviation of `hz` from the 12-TET frequency of the key in cents.
+    pub cents: f32,
+}
+
+/// The stretch-tuned frequencies of all 88 piano keys.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StretchTuning {
+    /// The keys in order from A0 to C8.
+    pub keys: Vec<StretchedKey>,
+}
+
+impl StretchTuning {
+    /// Stretch-tune the piano relative to A4 at 440hz.
+    pub fn new(profile: &InharmonicityProfile, method: StretchMethod) -> StretchTuning {
+        StretchTuning::with_tuning(profile, method, DEFAULT_TUNING)
+    }
+
+    /// Stretch-tune the piano relative to A4 at the frequency `a4`.
+    pub fn with_tuning(
+        profile: &InharmonicityProfile,
+        method: StretchMethod,
+        a4: calc::Hz,
+    ) -> StretchTuning {
+        let keys = match method {
+            StretchMethod::OctaveMatching { partial } => octave_matching(profile, partial, a4),
+            StretchMethod::Entropy { partials } => entropy_minimising(profile, partials, a4),
+            StretchMethod::Railsback { bass, treble } => (0..PIANO_KEYS)
+                .map(|index| {
+                    let step = piano_key(index).step() + railsback(index, bass, treble) / 100.0;
+                    hz_from_step_with_tuning(step, a4)
+                })
+                .collect(),
+        };
+        let keys = keys
+            .into_iter()
+            .enumerate()
+            .map(|(index, hz)| {
+                let key = piano_key(index);
+                StretchedKey {
+                    key,
+                    hz: Hz(hz),
+                    cents: (step_from_hz_with_tuning(hz, a4) - key.step()) * 100.0,
+                }
+            })
+            .collect();
+        StretchTuning { keys }
+    }
+
+    /// The stretched key, or `None` if it is not on the piano.
+    #[inline]
+    pub fn key(&self, key: LetterOctave) -> Option<&StretchedKey> {
+        piano_key_index(key).map(|index| &self.keys[index])
+    }
+
+    /// The stretched frequency of the key, or `None` if it is not on the piano.
+    #[inline]
+    pub fn hz(&self, key: LetterOctave) -> Option<Hz> {
+        self.key(key).map(|k| k.hz)
+    }
+
+    /// The deviation of the key from 12-TET in cents, or `None` if it is not on the piano.
+    #[inline]
+    pub fn cents(&self, key: LetterOctave) -> Option<f32> {
+        self.key(key).map(|k| k.cents)
+    }
+}
+
+/// The Railsback approximation of the deviation in cents at the given key index.
+fn railsback(index: usize, bass: f32, treble: f32) -> f32 {
+    if index < A4_KEY {
+        let x = (A4_KEY - index) as f32 / A4_KEY as f32;
+        bass * x * x * x
+    } else {
+        let x = (index - A4_KEY) as f32 / (PIANO_KEYS - 1 - A4_KEY) as f32;
+        treble * x * x * x
+    }
+}
+
+/// The frequency of each key when tuned by octave matching.
+fn octave_matching(profile: &InharmonicityProfile, partial: u32, a4: calc::Hz) -> Vec<calc::Hz> {
+    let m = partial.max(1) as f32;
+    let b = &profile.0;
+    // The ratio between the fundamentals of the upper and lower keys of a beatless octave.
+    let octave = |lower: usize| {
+        let upper = lower + 12;
+        2.0 * (1.0 + 4.0 * m * m * b[lower]).sqrt() / (1.0 + m * m * b[upper]).sqrt()
+    };
+
+    let mut hz = vec![0.0; PIANO_KEYS];
+    let a3 = a4 / octave(A3_KEY);
+    for (i, key) in (A3_KEY..=A4_KEY).enumerate() {
+        hz[key] = a3 * (a4 / a3).powf(i as f32 / 12.0);
+    }
+    for key in A4_KEY + 1..PIANO_KEYS {
+        hz[key] = hz[key - 12] * octave(key - 12);
+    }
+    for key in (0..A3_KEY).rev() {
+        hz[key] = hz[key + 12] / octave(key);
+    }
+    hz
+}
+
+/// The highest frequency of the partials included in the spectrum minimised by
+/// `entropy_minimising`.
+const MAX_SPECTRUM_HZ: calc::Hz = 10_000.0;
+/// The standard deviation of each partial within the spectrum, in cents.
+const PARTIAL_WIDTH_CENTS: f64 = 2.0;
+/// The steps in cents by which `entropy_minimising` moves the keys, from coarsest to finest.
+const ENTROPY_STEPS_CENTS: [f32; 5] = [4.0, 2.0, 1.0, 0.5, 0.25];
+/// The most passes over the keys made by `entropy_minimising` with each step.
+const MAX_ENTROPY_PASSES: usize = 32;
+
+/// The combined spectrum of the keys as a histogram with one bin per cent, along with the sums
+/// from which its Shannon entropy is found.
+struct Spectrum {
+    bins: Vec<f64>,
+    /// The frequency at the bottom of the first bin.
+    low: calc::Hz,
+    /// The sum of the bins.
+    total: f64,
+    /// The sum of `bin * ln(bin)` over the bins.
+    total_log: f64,
+}
+
+impl Spectrum {
+    /// An empty spectrum from `low` up to `MAX_SPECTRUM_HZ`.
+    fn new(low: calc::Hz) -> Spectrum {
+        // Leave room for the tails of the partials at either end.
+        let margin = 8.0 * PARTIAL_WIDTH_CENTS;
+        let len = (Spectrum::cents(low, MAX_SPECTRUM_HZ) + 2.0 * margin).ceil() as usize;
+        Spectrum {
+            bins: vec![0.0; len],
+            low: low / 2f32.powf((margin / 1200.0) as f32),
+            total: 0.0,
+            total_log: 0.0,
+        }
+    }
+
+    /// The distance in cents from `low` up to `hz`.
+    #[inline]
+    fn cents(low: calc::Hz, hz: calc::Hz) -> f64 {
+        1200.0 * f64::from(hz / low).log2()
+    }
+
+    /// The Shannon entropy of the spectrum, normalized to a probability distribution.
+    #[inline]
+    fn entropy(&self) -> f64 {
+        self.total.ln() - self.total_log / self.total
+    }
+
+    /// Add (with `sign: 1.0`) or remove (with `sign: -1.0`) the partials of a key.
+    fn add_key(&mut self, fundamental: calc::Hz, partials: &[u32], inharmonicity: f32, sign: f64) {
+        let reach = (4.0 * PARTIAL_WIDTH_CENTS).ceil() as isize;
+        for &n in partials {
+            let centre = Spectrum::cents(self.low, partial_hz(fundamental, n, inharmonicity));
+            let amplitude = sign / f64::from(n);
+            let nearest = centre.round() as isize;
+            let len = self.bins.len();
+            let bins = (nearest - reach..=nearest + reach)
+                .filter_map(|bin| usize::try_from(bin).ok())
+                .filter(|&bin| bin < len);
+            for bin in bins {
+                let distance = (bin as f64 - centre) / PARTIAL_WIDTH_CENTS;
+                let old = self.bins[bin];
+                let new = (old + amplitude * (-0.5 * distance * distance).exp()).max(0.0);
+                self.bins[bin] = new;
+                self.total += new - old;
+                self.total_log += x_ln_x(new) - x_ln_x(old);
+            }
+        }
+    }
+}
+
+/// `x * ln(x)`, which tends to `0` as `x` tends to `0`.
+#[inline]
+fn x_ln_x(x: f64) -> f64 {
+    if x > 0.0 {
+        x * x.ln()
+    } else {
+        0.0
+    }
+}
+
+/// The frequency of each key when tuned to minimise the entropy of the combined spectrum.
+fn entropy_minimising(
+    profile: &InharmonicityProfile,
+    partials: u32,
+    a4: calc::Hz,
+) -> Vec<calc::Hz> {
+    let b = &profile.0;
+    let mut hz = octave_matching(profile, 1, a4);
+    let mut spectrum = Spectrum::new(hz_from_step_with_tuning(LOWEST_KEY.step(), a4) / 2.0);
+    // The partials of each key, chosen once so that moving a key never changes which are counted.
+    let key_partials: Vec<Vec<u32>> = (0..PIANO_KEYS)
+        .map(|key| {
+            (1..=partials.max(1))
+                .take_while(|&n| partial_hz(hz[key], n, b[key]) < MAX_SPECTRUM_HZ)
+                .collect()
+        })
+        .collect();
+    for key in 0..PIANO_KEYS {
+        spectrum.add_key(hz[key], &key_partials[key], b[key], 1.0);
+    }
+
+    for &step in &ENTROPY_STEPS_CENTS {
+        let ratios = [2f32.powf(step / 1200.0), 2f32.powf(-step / 1200.0)];
+        for _ in 0..MAX_ENTROPY_PASSES {
+            let mut improved = false;
+            for key in (0..PIANO_KEYS).filter(|&key| key != A4_KEY) {
+                let entropy = spectrum.entropy();
+                spectrum.add_key(hz[key], &key_partials[key], b[key], -1.0);
+                let moved = ratios.iter().map(|ratio| hz[key] * ratio).find(|&moved| {
+                    spectrum.add_key(moved, &key_partials[key], b[key], 1.0);
+                    let lower = spectrum.entropy() < entropy - 1e-12;
+                    if !lower {
+                        spectrum.add_key(moved, &key_partials[key], b[key], -1.0);
+                    }
+                    lower
+                });
+                match moved {
+                    Some(moved) => {
+                        hz[key] = moved;
+                        improved = true;
+                    }
+                    None => spectrum.add_key(hz[key], &key_partials[key], b[key], 1.0),
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+    }
+    hz
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        assert_eq!(piano_key(0), LOWEST_KEY);
+        assert_eq!(piano_key(A4_KEY), LetterOctave(Letter::A, 4));
+        assert_eq!(piano_key(PIANO_KEYS - 1), HIGHEST_KEY);
+        assert_eq!(piano_key_index(LetterOctave(Letter::C, 4)), Some(39));
+        assert_eq!(piano_key_index(LetterOctave(Letter::Gsh, 0)), None);
+        assert_eq!(piano_key_index(LetterOctave(Letter::Csh, 8)), None);
+    }
+
+    #[test]
+    fn no_inharmonicity_is_equal_tempered() {
+        let method = StretchMethod::OctaveMatching { partial: 1 };
+        let tuning = StretchTuning::new(&InharmonicityProfile::uniform(0.0), method);
+        assert_eq!(tuning.keys.len(), PIANO_KEYS);
+        for key in &tuning.keys {
+            assert!(key.cents.abs() < 0.01, "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn octave_matching_stretches() {
+        let profile = InharmonicityProfile::typical();
+        let narrow = StretchTuning::new(&profile, StretchMethod::OctaveMatching { partial: 1 });
+        let wide = StretchTuning::new(&profile, StretchMethod::OctaveMatching { partial: 2 });
+        assert_eq!(narrow.hz(LetterOctave(Letter::A, 4)), Some(Hz(440.0)));
+        assert!(narrow.keys.windows(2).all(|w| w[0].hz < w[1].hz));
+        assert!(narrow.cents(LOWEST_KEY).unwrap() < 0.0);
+        assert!(narrow.cents(HIGHEST_KEY).unwrap() > 10.0);
+        assert!(wide.cents(LOWEST_KEY).unwrap() < narrow.cents(LOWEST_KEY).unwrap());
+        assert!(wide.cents(HIGHEST_KEY).unwrap() > narrow.cents(HIGHEST_KEY).unwrap());
+
+        // The second partial of A3 matches the fundamental of A4.
+        let a3 = narrow.hz(LetterOctave(Letter::A, 3)).unwrap();
+        let b = (
+            profile.get(LetterOctave(Letter::A, 3)).unwrap(),
+            profile.0[A4_KEY],
+        );
+        let a4_partial = crate::harmonic::partial_hz(440.0, 1, b.1);
+        assert!((a3.partials(2, b.0)[1].hz() - a4_partial).abs() < 1e-3);
+    }
+
+    #[test]
+    fn entropy_minimisation_stretches() {
+        let profile = InharmonicityProfile::typical();
+        let entropy = StretchMethod::Entropy { partials: 8 };
+        let tuning = StretchTuning::new(&profile, entropy);
+        assert_eq!(tuning.hz(LetterOctave(Letter::A, 4)), Some(Hz(440.0)));
+        assert!(tuning.keys.windows(2).all(|w| w[0].hz < w[1].hz));
+        assert!(tuning.cents(LOWEST_KEY).unwrap() < -5.0);
+        assert!(tuning.cents(HIGHEST_KEY).unwrap() > 10.0);
+
+        // Harmonic partials already coincide best close to 12-TET.
+        let harmonic = StretchTuning::new(&InharmonicityProfile::uniform(0.0), entropy);
+        for key in &harmonic.keys {
+            assert!(key.cents.abs() < 2.5, "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn railsback_curve() {
+        let tuning =
+            StretchTuning::with_tuning(&InharmonicityProfile::typical(), DEFAULT_RAILSBACK, 442.0);
+        assert!((tuning.cents(LOWEST_KEY).unwrap() + 30.0).abs() < 0.01);
+        assert!((tuning.cents(HIGHEST_KEY).unwrap() - 35.0).abs() < 0.01);
+        assert_eq!(tuning.hz(LetterOctave(Letter::A, 4)), Some(Hz(442.0)));
+        assert_eq!(tuning.key(LetterOctave(Letter::B, 8)), None);
+    }
+}
@@ -0,0 +1,409 @@
+//! Voice leading between chords, for harmonisation.
+//!
+//! A chord is a set of pitches, one per voice. The voice-leading distance between two chords is
+//! the smallest total number of semitones through which the voices must move to get from one to
+//! the other.
+
+use crate::contour::dtw;
+use crate::{calc, difference_in_semitones, Interval, Letter, LetterOctave, Pitch, Step};
+use std::ops::RangeInclusive;
+
+/// The movement of a single voice from one chord to the next.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Motion {
+    pub from: Step,
+    pub to: Step,
+}
+
+/// The perfect intervals that voices should not move in parallel.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParallelKind {
+    Unison,
+    Fifth,
+    Octave,
+}
+
+/// Two voices moving in parallel perfect intervals from one chord to the next.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Parallel {
+    /// The indices of the two voices, the first being the lesser.
+    pub voices: (usize, usize),
+    pub kind: ParallelKind,
+}
+
+/// Constraints on the voicings considered by `smoothest_voicing_with_options`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VoicingOptions {
+    /// The largest interval by which any voice may move.
+    pub max_leap: Interval,
+    /// Whether voices may move in parallel fifths, octaves or unisons.
+    pub allow_parallels: bool,
+    /// Whether a voice may lie above the voice after it, where voices are ordered from the lowest.
+    pub allow_crossing: bool,
+}
+
+impl Default for VoicingOptions {
+    fn default() -> VoicingOptions {
+        VoicingOptions {
+            max_leap: Interval::OCTAVE,
+            allow_parallels: false,
+            allow_crossing: false,
+        }
+    }
+}
+
+impl Motion {
+    /// The distance moved in semitones, positive when the voice rises.
+    #[inline]
+    pub fn semitones(self) -> calc::Step {
+        self.to.step() - self.from.step()
+    }
+}
+
+impl ParallelKind {
+    /// The kind of perfect interval between two pitches, or `None` if it is not a unison, fifth
+    /// or octave (including compound fifths and octaves).
+    pub fn of(interval: Interval) -> Option<ParallelKind> {
+        match interval.semitones().abs() {
+            0 => Some(ParallelKind::Unison),
+            s if s % 12 == 0 => Some(ParallelKind::Octave),
+            s if s % 12 == 7 => Some(ParallelKind::Fifth),
+            _ => None,
+        }
+    }
+}
+
+/// The steps of the chord in ascending order.
+fn sorted_steps<P: Pitch>(chord: &[P]) -> Vec<calc::Step> {
+    let mut steps: Vec<calc::Step> = chord.iter().map(|p| p.to_step().step()).collect();
+    steps.sort_by(f32::total_cmp);
+    steps
+}
+
+/// The motions of the voice leading between two chords with the least total movement.
+///
+/// Chords with the same number of notes are matched one to one, lowest to lowest. Otherwise notes
+/// are doubled as needed so that every note of each chord is matched, e.g. a triad leading to a
+/// seventh chord. Returns no motions if either chord is empty.
+pub fn voice_leading<P: Pitch, Q: Pitch>(from: &[P], to: &[Q]) -> Vec<Motion> {
+    let from = sorted_steps(from);
+    let to = sorted_steps(to);
+    let motion = |(i, j): (usize, usize)| Motion {
+        from: Step(from[i]),
+        to: Step(to[j]),
+    };
+    if from.is_empty() || to.is_empty() {
+        Vec::new()
+    } else if from.len() == to.len() {
+        (0..from.len()).map(|i| motion((i, i))).collect()
+    } else {
+        dtw(&from, &to).1.into_iter().map(motion).collect()
+    }
+}
+
+/// The total movement in semitones of the smoothest voice leading between two chords.
+///
+/// See `voice_leading` for how the notes of the chords are matched.
+pub fn voice_leading_distance<P: Pitch, Q: Pitch>(from: &[P], to: &[Q]) -> calc::Step {
+    voice_leading(from, to)
+        .into_iter()
+        .map(|m| m.semitones().abs())
+        .sum()
+}
+
+/// The kind of parallel motion between the voices `(a, b)` of two voicings, if any.
+fn parallel(
+    from: (LetterOctave, LetterOctave),
+    to: (LetterOctave, LetterOctave),
+) -> Option<ParallelKind> {
+    let a = (to.0 - from.0).semitones().signum();
+    let b = (to.1 - from.1).semitones().signum();
+    if a == 0 || a != b {
+        return None;
+    }
+    let kind = ParallelKind::of(from.1 - from.0)?;
+    (ParallelKind::of(to.1 - to.0) == Some(kind)).then_some(kind)
+}
+
+/// Every pair of voices moving in parallel unisons, fifths or octaves from `from` to `to`.
+///
+/// The `n`th note of each voicing belongs to the `n`th voice. Voices are only parallel when both
+/// move in the same direction, so repeated notes and contrary motion are not reported.
+pub fn parallels(from: &[LetterOctave], to: &[LetterOctave]) -> Vec<Parallel> {
+    let voices = from.len().min(to.len());
+    let mut parallels = Vec::new();
+    for i in 0..voices {
+        for j in i + 1..voices {
+            if let Some(kind) = parallel((from[i], from[j]), (to[i], to[j])) {
+                parallels.push(Parallel {
+                    voices: (i, j),
+                    kind,
+                });
+            }
+        }
+    }
+    parallels
+}
+
+/// The voicing of `chord` that moves the least from `previous`, with the default options.
+///
+/// See `smoothest_voicing_with_options`.
+pub fn smoothest_voicing(
+    previous: &[LetterOctave],
+    chord: &[Letter],
+    ranges: &[RangeInclusive<LetterOctave>],
+) -> Option<Vec<LetterOctave>> {
+    smoothest_voicing_with_options(previous, chord, ranges, VoicingOptions::default())
+}
+
+/// The voicing of `chord` that moves the least in total from `previous`, where the `n`th voice
+/// lies within `ranges[n]`.
+///
+/// Every letter of the chord is voiced when there are enough voices, and otherwise no letter is
+/// doubled. Returns `None` if no voicing satisfies the ranges and options, or if `previous` and
+/// `ranges` differ in length.
+pub fn smoothest_voicing_with_options(
+    previous: &[LetterOctave],
+    chord: &[Letter],
+    ranges: &[RangeInclusive<LetterOctave>],
+    options: VoicingOptions,
+) -> Option<Vec<LetterOctave>> {
+    let mut letters: Vec<Letter> = chord.to_vec();
+    letters.sort();
+    letters.dedup();
+    if letters.is_empty() || previous.len() != ranges.len() {
+        return None;
+    }
+
+    let max_leap = options.max_leap.semitones().abs();
+    let candidates: Vec<Vec<LetterOctave>> = previous
+        .iter()
+        .zip(ranges)
+        .map(|(&from, range)| {
+            let mut notes: Vec<LetterOctave> = (range.start().semitones()
+                ..=range.end().semitones())
+                .map(LetterOctave::from_semitones)
+                .filter(|note| letters.contains(&note.letter()))
+                .filter(|&note| (note - from).semitones().abs() <= max_leap)
+                .collect();
+            notes.sort_by_key(|&note| (note - from).semitones().abs());
+            notes
+        })
+        .collect();
+
+    // The least movement possible for each voice onwards, ignoring octaves, to bound the search.
+    let mut bounds = vec![0; previous.len() + 1];
+    for (voice, &from) in previous.iter().enumerate().rev() {
+        let nearest = letters
+            .iter()
+            .map(|&letter| difference_in_semitones(from.letter(), letter))
+            .min()
+            .unwrap_or(0);
+        bounds[voice] = bounds[voice + 1] + nearest;
+    }
+
+    let mut search = Search {
+        previous,
+        candidates: &candidates,
+        bounds: &bounds,
+        letters: &letters,
+        options,
+        voicing: Vec::with_capacity(previous.len()),
+        best: None,
+    };
+    search.visit(0);
+    search.best.map(|(_, voicing)| voicing)
+}
+
+/// The state of a branch and bound search for the smoothest voicing.
+struct Search<'a> {
+    previous: &'a [LetterOctave],
+    candidates: &'a [Vec<LetterOctave>],
+    bounds: &'a [calc::Semitones],
+    letters: &'a [Letter],
+    options: VoicingOptions,
+    voicing: Vec<LetterOctave>,
+    best: Option<(calc::Semitones, Vec<LetterOctave>)>,
+}
+
+impl Search<'_> {
+    /// The total movement of the voicing so far.
+    fn cost(&self) -> calc::Semitones {
+        let moves = self.voicing.iter().zip(self.previous);
+        moves
+            .map(|(&to, &from)| (to - from).semitones().abs())
+            .sum()
+    }
+
+    /// Try each candidate for the given voice, given the voicing of the voices below it.
+    fn visit(&mut self, voice: usize) {
+        let cost = self.cost();
+        if let Some((best, _)) = self.best {
+            if cost + self.bounds[voice] >= best {
+                return;
+            }
+        }
+        if voice == self.previous.len() {
+            if self.is_complete() {
+                self.best = Some((cost, self.voicing.clone()));
+            }
+            return;
+        }
+        for &note in &self.candidates[voice] {
+            if self.allows(voice, note) {
+                self.voicing.push(note);
+                self.visit(voice + 1);
+                self.voicing.pop();
+            }
+        }
+    }
+
+    /// Whether `note` may be added to the voicing as the given voice.
+    fn allows(&self, voice: usize, note: LetterOctave) -> bool {
+        if !self.options.allow_crossing && self.voicing.last().is_some_and(|&below| note < below) {
+            return false;
+        }
+        self.options.allow_parallels
+            || self.voicing.iter().enumerate().all(|(lower, &to)| {
+                let from = (self.previous[lower], self.previous[voice]);
+                parallel(from, (to, note)).is_none()
+            })
+    }
+
+    /// Whether the complete voicing contains every letter of the chord, or doubles none if there
+    /// are more letters than voices.
+    fn is_complete(&self) -> bool {
+        let voiced = |letter: &Letter| self.voicing.iter().any(|note| note.letter() == *letter);
+        let count = self.letters.iter().filter(|letter| voiced(letter)).count();
+        count == self.letters.len().min(self.voicing.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(notes: &[(Letter, i32)]) -> Vec<LetterOctave> {
+        notes.iter().map(|&(l, o)| LetterOctave(l, o)).collect()
+    }
+
+    #[test]
+    fn distance() {
+        let c = chord(&[(Letter::C, 4), (Letter::E, 4), (Letter::G, 4)]);
+        let f = chord(&[(Letter::C, 4), (Letter::F, 4), (Letter::A, 4)]);
+        assert_eq!(voice_leading_distance(&c, &f), 3.0);
+        // The order in which the notes are given does not matter.
+        let mut reversed = f.clone();
+        reversed.reverse();
+        assert_eq!(voice_leading_distance(&c, &reversed), 3.0);
+
+        let g7 = chord(&[
+            (Letter::G, 3),
+            (Letter::B, 3),
+            (Letter::D, 4),
+            (Letter::F, 4),
+        ]);
+        let c = chord(&[(Letter::G, 3), (Letter::C, 4), (Letter::E, 4)]);
+        let motions = voice_leading(&g7, &c);
+        assert_eq!(motions.len(), 4);
+        assert_eq!(voice_leading_distance(&g7, &c), 4.0);
+        assert!(voice_leading::<Step, Step>(&[], &[Step(60.0)]).is_empty());
+    }
+
+    #[test]
+    fn parallel_motion() {
+        let from = chord(&[
+            (Letter::C, 3),
+            (Letter::G, 3),
+            (Letter::E, 4),
+            (Letter::C, 5),
+        ]);
+        let to = chord(&[
+            (Letter::D, 3),
+            (Letter::A, 3),
+            (Letter::F, 4),
+            (Letter::D, 5),
+        ]);
+        assert_eq!(
+            parallels(&from, &to),
+            vec![
+                Parallel {
+                    voices: (0, 1),
+                    kind: ParallelKind::Fifth,
+                },
+                Parallel {
+                    voices: (0, 3),
+                    kind: ParallelKind::Octave,
+                },
+            ]
+        );
+        // The outer voices move by contrary motion from one octave to another, which is allowed.
+        let to = chord(&[
+            (Letter::D, 3),
+            (Letter::A, 3),
+            (Letter::F, 4),
+            (Letter::D, 4),
+        ]);
+        assert_eq!(
+            parallels(&from, &to),
+            vec![Parallel {
+                voices: (0, 1),
+                kind: ParallelKind::Fifth,
+            }]
+        );
+    }
+
+    #[test]
+    fn voicing_search() {
+        let ranges = [
+            LetterOctave(Letter::E, 2)..=LetterOctave(Letter::C, 4),
+            LetterOctave(Letter::C, 3)..=LetterOctave(Letter::G, 4),
+            LetterOctave(Letter::G, 3)..=LetterOctave(Letter::C, 5),
+            LetterOctave(Letter::C, 4)..=LetterOctave(Letter::G, 5),
+        ];
+        let c = chord(&[
+            (Letter::C, 3),
+            (Letter::G, 3),
+            (Letter::E, 4),
+            (Letter::C, 5),
+        ]);
+        let g = [Letter::G, Letter::B, Letter::D];
+        // The nearer B4 in the soprano would move in parallel octaves with the bass.
+        let voicing = smoothest_voicing(&c, &g, &ranges).unwrap();
+        assert_eq!(
+            voicing,
+            chord(&[
+                (Letter::B, 2),
+                (Letter::G, 3),
+                (Letter::D, 4),
+                (Letter::D, 5)
+            ])
+        );
+        assert!(parallels(&c, &voicing).is_empty());
+        assert_eq!(voice_leading_distance(&c, &voicing), 5.0);
+
+        // A step up from C major to D minor would be all parallels.
+        let d_minor = [Letter::D, Letter::F, Letter::A];
+        let voicing = smoothest_voicing(&c, &d_minor, &ranges).unwrap();
+        assert!(parallels(&c, &voicing).is_empty());
+        let options = VoicingOptions {
+            max_leap: Interval::MAJOR_SECOND,
+            allow_parallels: true,
+            ..VoicingOptions::default()
+        };
+        let parallel = smoothest_voicing_with_options(&c, &d_minor, &ranges, options).unwrap();
+        assert_eq!(
+            parallel,
+            chord(&[
+                (Letter::D, 3),
+                (Letter::A, 3),
+                (Letter::F, 4),
+                (Letter::D, 5)
+            ])
+        );
+
+        let narrow = [LetterOctave(Letter::C, 4)..=LetterOctave(Letter::D, 4)];
+        assert_eq!(smoothest_voicing(&c[..1], &g, &narrow), None);
+        assert_eq!(smoothest_voicing(&c, &g, &narrow), None);
+    }
+}
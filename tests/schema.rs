@@ -0,0 +1,94 @@
+#![cfg(feature = "schemars")]
+
+use pitch_calc::hz::Hz;
+use pitch_calc::letter::Letter;
+use pitch_calc::letter_octave::LetterOctave;
+use pitch_calc::perc::Perc;
+use pitch_calc::pitch::AnyPitch;
+use pitch_calc::scaled_perc::ScaledPerc;
+use pitch_calc::serde::scientific;
+use schemars::{schema_for, SchemaGenerator};
+use serde_json::json;
+
+#[test]
+fn test_hz_schema() {
+    let schema = schema_for!(Hz);
+
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+    assert_eq!(schema.get("type"), Some(&json!("number")));
+    assert_eq!(schema.get("minimum"), Some(&json!(0)));
+}
+
+#[test]
+fn test_scaled_perc_schema() {
+    let schema = schema_for!(ScaledPerc);
+
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+    assert_eq!(
+        schema.get("prefixItems").unwrap()[0],
+        json!({ "type": "number", "minimum": 0 })
+    );
+    assert_eq!(
+        schema.get("prefixItems").unwrap()[1],
+        json!({ "type": "number", "exclusiveMinimum": 0 })
+    );
+}
+
+#[test]
+fn test_bounds() {
+    let perc = schema_for!(Perc);
+    let minimum = perc.get("minimum").unwrap().as_f64().unwrap();
+    assert!(Perc(minimum).hz().abs() < 1e-3);
+    let letter_octave = schema_for!(LetterOctave);
+    let octave = &letter_octave.get("prefixItems").unwrap()[1];
+    for letter in [Letter::C, Letter::B] {
+        for bound in ["minimum", "maximum"] {
+            let octave = octave[bound].as_i64().unwrap() as i32;
+            assert!(serde_json::from_value::<LetterOctave>(json!([letter, octave])).is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_scientific_schema() {
+    let mut generator = SchemaGenerator::default();
+    let schema = scientific::schema::<Hz>(&mut generator);
+
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+    assert_eq!(
+        schema.get("anyOf").unwrap()[0],
+        json!({ "type": "string", "examples": ["440 Hz"] })
+    );
+    assert_eq!(
+        schema.get("anyOf").unwrap()[1],
+        json!({ "$ref": "#/$defs/Hz" })
+    );
+
+    let schema = scientific::schema::<Option<Vec<AnyPitch>>>(&mut generator);
+    let pitches = &schema.get("anyOf").unwrap()[1];
+    assert_eq!(pitches["type"], json!("array"));
+    let any_pitch = pitches["items"]["anyOf"].as_array().unwrap();
+    assert_eq!(any_pitch.len(), 3);
+    assert_eq!(any_pitch[2], json!({ "$ref": "#/$defs/AnyPitch" }));
+}
+
+#[test]
+fn test_any_pitch_schema() {
+    let schema = schema_for!(AnyPitch);
+
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+    let variants = schema.get("oneOf").unwrap().as_array().unwrap();
+    assert_eq!(variants.len(), 8);
+    assert_eq!(variants[0]["required"], json!(["Hz"]));
+    assert_eq!(
+        variants[7]["properties"]["LetterOctave"],
+        json!({ "$ref": "#/$defs/LetterOctave" })
+    );
+    let letter_octave = &schema.get("$defs").unwrap()["LetterOctave"];
+    assert_eq!(
+        letter_octave["prefixItems"][0],
+        json!({ "$ref": "#/$defs/Letter" })
+    );
+    let value = serde_json::to_value(AnyPitch::from(LetterOctave(Letter::C, 4))).unwrap();
+    assert_eq!(value, json!({ "LetterOctave": ["C", 4] }));
+}
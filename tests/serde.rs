@@ -1,10 +1,12 @@
 #![cfg(feature = "serde")]
 
-use pitch_calc::hz::Hz;
+use pitch_calc::hz::{BinMapper, FrequencyRange, Hz};
+use pitch_calc::instrument::{self, Instrument};
 use pitch_calc::letter::Letter;
 use pitch_calc::letter_octave::LetterOctave;
-use pitch_calc::mel::Mel;
+use pitch_calc::mel::{Mel, Slaney};
 use pitch_calc::perc::Perc;
+use pitch_calc::pitch::AnyPitch;
 use pitch_calc::scaled_perc::ScaledPerc;
 use pitch_calc::step::Step;
 
@@ -52,7 +54,7 @@ fn test_letter_octave() {
 
 #[test]
 fn test_mel() {
-    let mel = Mel(440.0);
+    let mel = Mel::new(440.0);
     let serialized = serde_json::to_string(&mel).unwrap();
 
     println!("{}", serialized);
@@ -62,6 +64,12 @@ fn test_mel() {
 
     println!("{:?}", deserialized);
     assert_eq!(mel, deserialized);
+
+    // The scale is part of the type, so is not serialized.
+    let slaney = Mel(15.0, Slaney);
+    assert_eq!("15.0", &serde_json::to_string(&slaney).unwrap());
+    let deserialized: Mel<Slaney> = serde_json::from_str("15.0").unwrap();
+    assert!((deserialized.hz() - 1000.0).abs() < 1e-2);
 }
 
 #[test]
@@ -105,3 +113,197 @@ fn test_step() {
     println!("{:?}", deserialized);
     assert_eq!(step, deserialized);
 }
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Voice {
+    #[serde(with = "pitch_calc::serde::scientific")]
+    key: Letter,
+    #[serde(with = "pitch_calc::serde::scientific")]
+    lowest: LetterOctave,
+    #[serde(with = "pitch_calc::serde::scientific")]
+    tuning: Hz,
+    #[serde(with = "pitch_calc::serde::scientific")]
+    step: Step,
+    #[serde(with = "pitch_calc::serde::scientific")]
+    mel: Mel,
+    #[serde(with = "pitch_calc::serde::scientific")]
+    level: Perc,
+    #[serde(with = "pitch_calc::serde::scientific")]
+    knob: ScaledPerc,
+    #[serde(with = "pitch_calc::serde::scientific")]
+    pitches: Vec<AnyPitch>,
+    #[serde(with = "pitch_calc::serde::scientific")]
+    highest: Option<LetterOctave>,
+}
+
+fn voice() -> Voice {
+    Voice {
+        key: Letter::Csh,
+        lowest: LetterOctave(Letter::Bb, 2),
+        tuning: Hz(442.5),
+        step: Step(60.5),
+        mel: Mel::new(549.5),
+        level: Perc(0.25),
+        knob: ScaledPerc(0.5, 4.0),
+        pitches: vec![LetterOctave(Letter::A, 4).into(), Hz(27.5).into()],
+        highest: None,
+    }
+}
+
+#[test]
+fn test_scientific_human_readable() {
+    let serialized = serde_json::to_string(&voice()).unwrap();
+
+    println!("{}", serialized);
+    assert_eq!(
+        concat!(
+            r#"{"key":"C#","lowest":"Bb2","tuning":"442.5 Hz","step":"60.5m","mel":"549.5 mel","#,
+            r#""level":"25%","knob":["50%",4.0],"pitches":["A4","27.5 Hz"],"highest":null}"#
+        ),
+        &serialized
+    );
+
+    let deserialized: Voice = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(voice(), deserialized);
+}
+
+#[test]
+fn test_scientific_compact() {
+    let voice = voice();
+    let serialized = bincode::serialize(&voice).unwrap();
+    let default = bincode::serialize(&(
+        voice.key,
+        voice.lowest,
+        voice.tuning,
+        voice.step,
+        voice.mel,
+        voice.level,
+        voice.knob,
+        &voice.pitches,
+        voice.highest,
+    ))
+    .unwrap();
+    assert_eq!(default, serialized);
+
+    let deserialized: Voice = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(voice, deserialized);
+}
+
+#[test]
+fn test_scientific_parsing() {
+    #[derive(serde::Deserialize)]
+    struct Pitches {
+        #[serde(with = "pitch_calc::serde::scientific")]
+        mel: Mel,
+        #[serde(with = "pitch_calc::serde::scientific")]
+        step: Step,
+        #[serde(with = "pitch_calc::serde::scientific")]
+        any: Vec<AnyPitch>,
+    }
+
+    let pitches: Pitches =
+        serde_json::from_str(r#"{"mel":"1.2 kHz","step":"A4","any":["69M"]}"#).unwrap();
+    assert!((pitches.mel.hz() - 1200.0).abs() < 1e-1);
+    assert!((pitches.step.step() - 69.0).abs() < 1e-4);
+    assert_eq!(pitches.any, vec![AnyPitch::Step(Step(69.0))]);
+
+    // The default representation is also accepted.
+    let pitches: Pitches = serde_json::from_str(
+        r#"{"mel":549.64,"step":60,"any":[{"Hz":440.0},[0.5,4.0],["50%",4.0]]}"#,
+    )
+    .unwrap();
+    assert!((pitches.mel.hz() - 440.0).abs() < 1e-1);
+    assert_eq!(pitches.step, Step(60.0));
+    assert_eq!(pitches.any[0], AnyPitch::Hz(Hz(440.0)));
+    assert_eq!(pitches.any[1], pitches.any[2]);
+
+    #[derive(serde::Deserialize)]
+    struct Tuning {
+        #[serde(with = "pitch_calc::serde::scientific")]
+        key: Letter,
+        #[serde(with = "pitch_calc::serde::scientific")]
+        lowest: LetterOctave,
+        #[serde(with = "pitch_calc::serde::scientific")]
+        tuning: Hz,
+    }
+
+    let tuning: Tuning =
+        serde_json::from_str(r#"{"key":"Csh","lowest":["Bb",2],"tuning":442.5}"#).unwrap();
+    assert_eq!(tuning.key, Letter::Csh);
+    assert_eq!(tuning.lowest, LetterOctave(Letter::Bb, 2));
+    assert_eq!(tuning.tuning, Hz(442.5));
+    let error = serde_json::from_str::<Tuning>(r#"{"key":"C","lowest":"A4","tuning":-1}"#)
+        .err()
+        .unwrap();
+    assert!(
+        error.to_string().contains("must not be negative"),
+        "{}",
+        error
+    );
+
+    let error = serde_json::from_str::<Voice>(r#"{"key":"H"}"#).unwrap_err();
+    assert!(
+        error.to_string().contains("invalid pitch \"H\""),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_validation() {
+    let error = serde_json::from_str::<Hz>("-5.0").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "frequency must not be negative but was -5hz"
+    );
+    assert!(serde_json::from_str::<Hz>("0.0").is_ok());
+
+    let error = serde_json::from_str::<ScaledPerc>("[0.5,0.0]").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "scale weight must be positive and finite but was 0"
+    );
+    assert!(serde_json::from_str::<ScaledPerc>("[0.5,-4.0]").is_err());
+    let error = serde_json::from_str::<ScaledPerc>("[-0.5,4.0]").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "scaled percentage must not be negative but was -0.5"
+    );
+
+    let error = serde_json::from_str::<FrequencyRange>(r#"{"min":100.0,"max":10.0}"#).unwrap_err();
+    assert_eq!(error.to_string(), "invalid frequency range 100hz..10hz");
+    assert!(serde_json::from_str::<FrequencyRange>(r#"{"min":20.0,"max":20000.0}"#).is_ok());
+
+    let error =
+        serde_json::from_str::<BinMapper>(r#"{"sample_rate":44100.0,"fft_size":0}"#).unwrap_err();
+    assert_eq!(error.to_string(), "FFT size must not be zero");
+    assert!(
+        serde_json::from_str::<BinMapper>(r#"{"sample_rate":44100.0,"fft_size":4096}"#).is_ok()
+    );
+
+    let error = serde_json::from_str::<LetterOctave>(r#"["A",2000000000]"#).unwrap_err();
+    assert_eq!(error.to_string(), "octave is out of range");
+    assert!(serde_json::from_str::<AnyPitch>(r#"{"Mel":-1.0}"#).is_err());
+
+    let infinite = bincode::serialize(&f32::INFINITY).unwrap();
+    assert!(bincode::deserialize::<Step>(&infinite).is_err());
+
+    let error = serde_json::from_str::<Voice>(r#"{"key":"C","lowest":"A4","tuning":"-440 Hz"}"#)
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("must not be negative"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_instrument() {
+    let serialized = serde_json::to_string(&instrument::HORN_IN_F).unwrap();
+    assert_eq!(
+        r#"{"name":"Horn in F","transposition":-7,"written_range":{"start":["Fsh",2],"end":["C",6]}}"#,
+        &serialized
+    );
+    let deserialized: Instrument = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(instrument::HORN_IN_F, deserialized);
+}